@@ -0,0 +1,894 @@
+use anchor_lang::prelude::*;
+use task_escrow::state::{Contribution, Escrow};
+use task_manager::cpi::accounts::{ExtendDeadline, UpdateTaskState};
+use task_manager::program::TaskManager;
+use task_manager::state::{
+    ApprovalModel, BountyEntry, RecipientSplit, Task, TaskKind, TaskState, WinnerSelection,
+    TOTAL_SHARE_BPS,
+};
+
+pub mod state;
+use state::*;
+
+declare_id!("Appr1111111111111111111111111111111111111111");
+
+/// Minimum contribution to have voting power: $10 USDC (6 decimals)
+pub const MIN_CONTRIBUTION_FOR_VOTE: u64 = 10_000_000;
+
+/// Quorum: 50% of net contributed value must vote
+pub const QUORUM_PERCENTAGE: u8 = 50;
+
+/// Approval threshold: 60% of voted weight must approve
+pub const APPROVAL_THRESHOLD_PERCENTAGE: u8 = 60;
+
+/// Minimum unique voters required
+pub const MIN_VOTERS: u32 = 3;
+
+/// Deadline extension passes when more than 50% of net contributed value approves
+pub const EXTENSION_THRESHOLD_PERCENTAGE: u8 = 50;
+
+#[program]
+pub mod approval_vote {
+    use super::*;
+
+    /// Cast or change an approve/reject vote on submitted work
+    /// Vote weight = contributor's net contribution to this task (USDC amount, not per-wallet)
+    pub fn cast_approval_vote(ctx: Context<CastApprovalVote>, approve: bool) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        let review_round = task.proof_submitted_at.unwrap_or_default();
+        require!(
+            clock.unix_timestamp <= review_round + task.review_window_seconds,
+            ApprovalVoteError::ReviewWindowClosed
+        );
+
+        let contribution = &ctx.accounts.contribution;
+        let vote_weight = contribution.amount.saturating_sub(contribution.refund_amount);
+        require!(
+            vote_weight >= MIN_CONTRIBUTION_FOR_VOTE,
+            ApprovalVoteError::ContributionTooSmall
+        );
+
+        let tally = &mut ctx.accounts.approval_tally;
+        if tally.task == Pubkey::default() {
+            tally.task = task.key();
+            tally.bump = ctx.bumps.approval_tally;
+        }
+        if tally.review_round != review_round {
+            // Resubmission after a revision starts a fresh review
+            tally.reset(review_round);
+        }
+
+        // Replace this voter's earlier ballot in the current round
+        let vote = &mut ctx.accounts.approval_vote;
+        let updated = vote.voter != Pubkey::default() && vote.review_round == review_round;
+        if updated {
+            if vote.approve {
+                tally.approve_weight -= vote.vote_weight;
+            } else {
+                tally.reject_weight -= vote.vote_weight;
+            }
+        } else {
+            tally.total_voters += 1;
+        }
+
+        if approve {
+            tally.approve_weight = tally.approve_weight.checked_add(vote_weight).unwrap();
+        } else {
+            tally.reject_weight = tally.reject_weight.checked_add(vote_weight).unwrap();
+        }
+
+        vote.task = task.key();
+        vote.voter = ctx.accounts.voter.key();
+        vote.approve = approve;
+        vote.vote_weight = vote_weight;
+        vote.review_round = review_round;
+        vote.voted_at = clock.unix_timestamp;
+
+        emit!(ApprovalVoteCast {
+            task: task.key(),
+            voter: ctx.accounts.voter.key(),
+            approve,
+            vote_weight,
+            updated,
+            voted_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Close the review after the window and apply the outcome in task_manager
+    /// `feedback_hash` is forwarded to reject_task when the vote fails
+    pub fn finalize_approval(ctx: Context<FinalizeApproval>, feedback_hash: [u8; 32]) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let tally = &ctx.accounts.approval_tally;
+        let clock = Clock::get()?;
+
+        let review_round = task.proof_submitted_at.unwrap_or_default();
+        require!(
+            tally.review_round == review_round,
+            ApprovalVoteError::QuorumNotMet
+        );
+        require!(
+            clock.unix_timestamp > review_round + task.review_window_seconds,
+            ApprovalVoteError::ReviewWindowOpen
+        );
+
+        let escrow = &ctx.accounts.escrow;
+        let net_contributed = escrow.total_contributed.saturating_sub(escrow.total_refunded);
+        require!(
+            tally.meets_quorum(net_contributed, QUORUM_PERCENTAGE, MIN_VOTERS),
+            ApprovalVoteError::QuorumNotMet
+        );
+
+        let approved = tally.is_approved(APPROVAL_THRESHOLD_PERCENTAGE);
+
+        emit!(ApprovalFinalized {
+            task: task.key(),
+            approved,
+            approve_weight: tally.approve_weight,
+            reject_weight: tally.reject_weight,
+            total_voters: tally.total_voters,
+            finalized_at: clock.unix_timestamp,
+        });
+
+        // CPI to task_manager, signed by the tally PDA
+        let task_key = task.key();
+        let seeds = &[
+            b"approval_tally",
+            task_key.as_ref(),
+            &[tally.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = UpdateTaskState {
+            task: ctx.accounts.task.to_account_info(),
+            task_history: ctx.accounts.task_history.to_account_info(),
+            authority: ctx.accounts.approval_tally.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.task_manager_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        if approved {
+            task_manager::cpi::approve_task(cpi_ctx)
+        } else {
+            task_manager::cpi::reject_task(cpi_ctx, feedback_hash)
+        }
+    }
+
+    /// Recipient asks donors for a later deadline
+    /// Replaces any earlier proposal and resets its votes (bumping the nonce
+    /// so votes on the earlier proposal no longer count)
+    pub fn propose_deadline_extension(
+        ctx: Context<ProposeDeadlineExtension>,
+        new_deadline: i64,
+        justification_uri: String,
+    ) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        require!(
+            task.can_extend_deadline(clock.unix_timestamp),
+            ApprovalVoteError::DeadlineNotExtendable
+        );
+        require!(
+            new_deadline > task.deadline.unwrap(),
+            ApprovalVoteError::InvalidDeadline
+        );
+        require!(
+            !justification_uri.is_empty() && justification_uri.len() <= MAX_JUSTIFICATION_URI_LEN,
+            ApprovalVoteError::InvalidJustificationUri
+        );
+
+        let extension = &mut ctx.accounts.extension;
+        extension.task = task.key();
+        extension.recipient = ctx.accounts.recipient.key();
+        extension.new_deadline = new_deadline;
+        extension.justification_uri = justification_uri.clone();
+        extension.proposed_at = clock.unix_timestamp;
+        extension.nonce = extension.nonce.checked_add(1).unwrap();
+        extension.approve_weight = 0;
+        extension.reject_weight = 0;
+        extension.total_voters = 0;
+        extension.executed = false;
+        extension.bump = ctx.bumps.extension;
+
+        emit!(DeadlineExtensionProposed {
+            task: task.key(),
+            recipient: ctx.accounts.recipient.key(),
+            current_deadline: task.deadline.unwrap(),
+            new_deadline,
+            justification_uri,
+            nonce: extension.nonce,
+            proposed_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Vote on a deadline extension, weighted by net contribution
+    /// Applies the extension in task_manager as soon as it passes
+    pub fn cast_extension_vote(ctx: Context<CastExtensionVote>, approve: bool) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        // Must pass before the old deadline
+        require!(
+            task.can_extend_deadline(clock.unix_timestamp),
+            ApprovalVoteError::DeadlineNotExtendable
+        );
+
+        let contribution = &ctx.accounts.contribution;
+        let vote_weight = contribution.amount.saturating_sub(contribution.refund_amount);
+        require!(
+            vote_weight >= MIN_CONTRIBUTION_FOR_VOTE,
+            ApprovalVoteError::ContributionTooSmall
+        );
+
+        let extension = &mut ctx.accounts.extension;
+        let vote = &mut ctx.accounts.extension_vote;
+        let updated = vote.voter != Pubkey::default() && vote.proposal_nonce == extension.nonce;
+        if updated {
+            extension
+                .remove_vote(vote.approve, vote.vote_weight)
+                .ok_or(ApprovalVoteError::ExtensionTallyMismatch)?;
+        } else {
+            extension.total_voters += 1;
+        }
+
+        extension
+            .add_vote(approve, vote_weight)
+            .ok_or(ApprovalVoteError::ExtensionTallyMismatch)?;
+
+        vote.task = task.key();
+        vote.voter = ctx.accounts.voter.key();
+        vote.approve = approve;
+        vote.vote_weight = vote_weight;
+        vote.proposal_nonce = extension.nonce;
+        vote.voted_at = clock.unix_timestamp;
+
+        let escrow = &ctx.accounts.escrow;
+        let net_contributed = escrow.total_contributed.saturating_sub(escrow.total_refunded);
+        let passed = extension.passes(net_contributed, EXTENSION_THRESHOLD_PERCENTAGE);
+
+        emit!(ExtensionVoteCast {
+            task: task.key(),
+            voter: ctx.accounts.voter.key(),
+            approve,
+            vote_weight,
+            updated,
+            passed,
+            voted_at: clock.unix_timestamp,
+        });
+
+        if !passed {
+            return Ok(());
+        }
+
+        extension.executed = true;
+        let new_deadline = extension.new_deadline;
+
+        // CPI to task_manager, signed by the extension PDA
+        let task_key = task.key();
+        let seeds = &[
+            b"deadline_extension",
+            task_key.as_ref(),
+            &[extension.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = ExtendDeadline {
+            task: ctx.accounts.task.to_account_info(),
+            authority: ctx.accounts.extension.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.task_manager_program.to_account_info();
+        task_manager::cpi::extend_deadline(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
+            new_deadline,
+        )
+    }
+
+    /// Veto a hybrid task's reviewer approval during the veto window
+    /// Once vetoing weight exceeds the task's threshold, the task moves to Disputed
+    pub fn cast_veto(ctx: Context<CastVeto>) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        require!(
+            task.is_veto_window_open(clock.unix_timestamp),
+            ApprovalVoteError::VetoWindowClosed
+        );
+        let veto_round = task.veto_window_ends_at.unwrap_or_default();
+
+        let contribution = &ctx.accounts.contribution;
+        let vote_weight = contribution.amount.saturating_sub(contribution.refund_amount);
+        require!(
+            vote_weight >= MIN_CONTRIBUTION_FOR_VOTE,
+            ApprovalVoteError::ContributionTooSmall
+        );
+
+        let veto_tally = &mut ctx.accounts.veto_tally;
+        if veto_tally.task == Pubkey::default() {
+            veto_tally.task = task.key();
+            veto_tally.bump = ctx.bumps.veto_tally;
+        }
+        if veto_tally.veto_round != veto_round {
+            veto_tally.reset(veto_round);
+        }
+
+        let record = &mut ctx.accounts.veto_record;
+        require!(
+            record.voter == Pubkey::default() || record.veto_round != veto_round,
+            ApprovalVoteError::AlreadyVetoed
+        );
+        record.task = task.key();
+        record.voter = ctx.accounts.voter.key();
+        record.vote_weight = vote_weight;
+        record.veto_round = veto_round;
+        record.vetoed_at = clock.unix_timestamp;
+
+        veto_tally.veto_weight = veto_tally.veto_weight.checked_add(vote_weight).unwrap();
+        veto_tally.total_vetoers += 1;
+
+        let escrow = &ctx.accounts.escrow;
+        let net_contributed = escrow.total_contributed.saturating_sub(escrow.total_refunded);
+        let vetoed = veto_tally.exceeds_threshold(net_contributed, task.veto_threshold_bps);
+
+        emit!(VetoCast {
+            task: task.key(),
+            voter: ctx.accounts.voter.key(),
+            vote_weight,
+            total_veto_weight: veto_tally.veto_weight,
+            vetoed,
+            vetoed_at: clock.unix_timestamp,
+        });
+
+        if !vetoed {
+            return Ok(());
+        }
+
+        // CPI to task_manager, signed by the veto tally PDA
+        let task_key = task.key();
+        let seeds = &[
+            b"veto_tally",
+            task_key.as_ref(),
+            &[veto_tally.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = UpdateTaskState {
+            task: ctx.accounts.task.to_account_info(),
+            task_history: ctx.accounts.task_history.to_account_info(),
+            authority: ctx.accounts.veto_tally.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.task_manager_program.to_account_info();
+        task_manager::cpi::veto_approval(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer))
+    }
+
+    /// Vote for a bounty entry once entries have closed
+    /// Votes are final; the entry with the most net contributed weight wins
+    pub fn cast_bounty_vote(ctx: Context<CastBountyVote>) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        require!(
+            task.can_select_winners(clock.unix_timestamp),
+            ApprovalVoteError::BountyVotingClosed
+        );
+
+        let contribution = &ctx.accounts.contribution;
+        let vote_weight = contribution.amount.saturating_sub(contribution.refund_amount);
+        require!(
+            vote_weight >= MIN_CONTRIBUTION_FOR_VOTE,
+            ApprovalVoteError::ContributionTooSmall
+        );
+
+        let participant = ctx.accounts.entry.participant;
+        let tally = &mut ctx.accounts.bounty_tally;
+        if tally.task == Pubkey::default() {
+            tally.task = task.key();
+            tally.bump = ctx.bumps.bounty_tally;
+        }
+        let candidate = &mut ctx.accounts.candidate;
+        if candidate.task == Pubkey::default() {
+            candidate.task = task.key();
+            candidate.participant = participant;
+            candidate.bump = ctx.bumps.candidate;
+        }
+        tally.record_vote(candidate, vote_weight);
+
+        let vote = &mut ctx.accounts.bounty_vote;
+        vote.task = task.key();
+        vote.voter = ctx.accounts.voter.key();
+        vote.participant = participant;
+        vote.vote_weight = vote_weight;
+        vote.voted_at = clock.unix_timestamp;
+
+        emit!(BountyVoteCast {
+            task: task.key(),
+            voter: ctx.accounts.voter.key(),
+            participant,
+            vote_weight,
+            leader: tally.leader,
+            voted_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Award a donor-voted bounty to the leading entry once quorum is met
+    /// Permissionless; the tally PDA signs the winner selection
+    pub fn finalize_bounty_vote(ctx: Context<FinalizeBountyVote>) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let tally = &ctx.accounts.bounty_tally;
+        let clock = Clock::get()?;
+
+        require!(
+            task.can_select_winners(clock.unix_timestamp),
+            ApprovalVoteError::BountyVotingClosed
+        );
+
+        let escrow = &ctx.accounts.escrow;
+        let net_contributed = escrow.total_contributed.saturating_sub(escrow.total_refunded);
+        require!(
+            tally.meets_quorum(net_contributed, QUORUM_PERCENTAGE, MIN_VOTERS),
+            ApprovalVoteError::QuorumNotMet
+        );
+
+        emit!(BountyVoteFinalized {
+            task: task.key(),
+            winner: tally.leader,
+            winner_weight: tally.leader_weight,
+            total_weight: tally.total_weight,
+            total_voters: tally.total_voters,
+            finalized_at: clock.unix_timestamp,
+        });
+
+        // CPI to task_manager, signed by the tally PDA
+        let task_key = task.key();
+        let seeds = &[
+            b"bounty_tally",
+            task_key.as_ref(),
+            &[tally.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let winners = vec![RecipientSplit {
+            recipient: tally.leader,
+            share_bps: TOTAL_SHARE_BPS,
+        }];
+
+        let cpi_accounts = UpdateTaskState {
+            task: ctx.accounts.task.to_account_info(),
+            task_history: ctx.accounts.task_history.to_account_info(),
+            authority: ctx.accounts.bounty_tally.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.task_manager_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer)
+            .with_remaining_accounts(vec![ctx.accounts.leader_entry.to_account_info()]);
+
+        task_manager::cpi::select_bounty_winners(cpi_ctx, winners)
+    }
+}
+
+#[derive(Accounts)]
+pub struct CastApprovalVote<'info> {
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = ApprovalVote::LEN,
+        seeds = [b"approval_vote", task.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub approval_vote: Account<'info, ApprovalVote>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = ApprovalTally::LEN,
+        seeds = [b"approval_tally", task.key().as_ref()],
+        bump
+    )]
+    pub approval_tally: Account<'info, ApprovalTally>,
+
+    #[account(
+        constraint = task.state == TaskState::SubmittedForReview @ ApprovalVoteError::ReviewNotActive,
+        constraint = task.approval_model == ApprovalModel::DonorVote @ ApprovalVoteError::WrongApprovalModel
+    )]
+    pub task: Account<'info, Task>,
+
+    /// Voter's contribution receipt from task_escrow (source of vote weight)
+    #[account(
+        seeds = [b"contribution", task.key().as_ref(), voter.key().as_ref()],
+        bump,
+        seeds::program = task_escrow::ID,
+        constraint = contribution.task == task.key() @ ApprovalVoteError::InvalidContribution,
+        constraint = contribution.contributor == voter.key() @ ApprovalVoteError::InvalidContribution
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeApproval<'info> {
+    #[account(
+        seeds = [b"approval_tally", task.key().as_ref()],
+        bump = approval_tally.bump
+    )]
+    pub approval_tally: Account<'info, ApprovalTally>,
+
+    #[account(
+        mut,
+        constraint = task.state == TaskState::SubmittedForReview @ ApprovalVoteError::ReviewNotActive,
+        constraint = task.approval_model == ApprovalModel::DonorVote @ ApprovalVoteError::WrongApprovalModel
+    )]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        seeds = [b"escrow", task.key().as_ref()],
+        bump = escrow.bump,
+        seeds::program = task_escrow::ID
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// CHECK: Task history PDA, validated by task_manager
+    #[account(mut)]
+    pub task_history: UncheckedAccount<'info>,
+
+    pub task_manager_program: Program<'info, TaskManager>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CastVeto<'info> {
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VetoRecord::LEN,
+        seeds = [b"veto", task.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub veto_record: Account<'info, VetoRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VetoTally::LEN,
+        seeds = [b"veto_tally", task.key().as_ref()],
+        bump
+    )]
+    pub veto_tally: Account<'info, VetoTally>,
+
+    #[account(
+        mut,
+        constraint = task.state == TaskState::SubmittedForReview @ ApprovalVoteError::ReviewNotActive,
+        constraint = task.approval_model == ApprovalModel::Hybrid @ ApprovalVoteError::WrongApprovalModel
+    )]
+    pub task: Account<'info, Task>,
+
+    /// Voter's contribution receipt from task_escrow (source of veto weight)
+    #[account(
+        seeds = [b"contribution", task.key().as_ref(), voter.key().as_ref()],
+        bump,
+        seeds::program = task_escrow::ID,
+        constraint = contribution.task == task.key() @ ApprovalVoteError::InvalidContribution,
+        constraint = contribution.contributor == voter.key() @ ApprovalVoteError::InvalidContribution
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(
+        seeds = [b"escrow", task.key().as_ref()],
+        bump = escrow.bump,
+        seeds::program = task_escrow::ID
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    /// CHECK: Task history PDA, validated by task_manager
+    #[account(mut)]
+    pub task_history: UncheckedAccount<'info>,
+
+    pub task_manager_program: Program<'info, TaskManager>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeDeadlineExtension<'info> {
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        space = DeadlineExtension::LEN,
+        seeds = [b"deadline_extension", task.key().as_ref()],
+        bump
+    )]
+    pub extension: Account<'info, DeadlineExtension>,
+
+    #[account(
+        constraint = task.recipient == Some(recipient.key()) @ ApprovalVoteError::UnauthorizedRecipient
+    )]
+    pub task: Account<'info, Task>,
+
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastExtensionVote<'info> {
+    #[account(
+        mut,
+        seeds = [b"deadline_extension", task.key().as_ref()],
+        bump = extension.bump,
+        constraint = !extension.executed @ ApprovalVoteError::ExtensionAlreadyExecuted
+    )]
+    pub extension: Account<'info, DeadlineExtension>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = ExtensionVote::LEN,
+        seeds = [b"extension_vote", task.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub extension_vote: Account<'info, ExtensionVote>,
+
+    #[account(mut)]
+    pub task: Account<'info, Task>,
+
+    /// Voter's contribution receipt from task_escrow (source of vote weight)
+    #[account(
+        seeds = [b"contribution", task.key().as_ref(), voter.key().as_ref()],
+        bump,
+        seeds::program = task_escrow::ID,
+        constraint = contribution.task == task.key() @ ApprovalVoteError::InvalidContribution,
+        constraint = contribution.contributor == voter.key() @ ApprovalVoteError::InvalidContribution
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(
+        seeds = [b"escrow", task.key().as_ref()],
+        bump = escrow.bump,
+        seeds::program = task_escrow::ID
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub task_manager_program: Program<'info, TaskManager>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastBountyVote<'info> {
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = BountyTally::LEN,
+        seeds = [b"bounty_tally", task.key().as_ref()],
+        bump
+    )]
+    pub bounty_tally: Account<'info, BountyTally>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = BountyCandidate::LEN,
+        seeds = [b"bounty_candidate", task.key().as_ref(), entry.participant.as_ref()],
+        bump
+    )]
+    pub candidate: Account<'info, BountyCandidate>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = BountyVote::LEN,
+        seeds = [b"bounty_vote", task.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub bounty_vote: Account<'info, BountyVote>,
+
+    /// Entry being voted for, from task_manager
+    #[account(
+        seeds = [b"bounty_entry", task.key().as_ref(), entry.participant.as_ref()],
+        bump = entry.bump,
+        seeds::program = task_manager::ID,
+        constraint = entry.has_submitted() @ ApprovalVoteError::EntryNotSubmitted
+    )]
+    pub entry: Account<'info, BountyEntry>,
+
+    #[account(
+        constraint = task.kind == TaskKind::Bounty { selection: WinnerSelection::DonorVote }
+            @ ApprovalVoteError::WrongApprovalModel
+    )]
+    pub task: Account<'info, Task>,
+
+    /// Voter's contribution receipt from task_escrow (source of vote weight)
+    #[account(
+        seeds = [b"contribution", task.key().as_ref(), voter.key().as_ref()],
+        bump,
+        seeds::program = task_escrow::ID,
+        constraint = contribution.task == task.key() @ ApprovalVoteError::InvalidContribution,
+        constraint = contribution.contributor == voter.key() @ ApprovalVoteError::InvalidContribution
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeBountyVote<'info> {
+    #[account(
+        seeds = [b"bounty_tally", task.key().as_ref()],
+        bump = bounty_tally.bump
+    )]
+    pub bounty_tally: Account<'info, BountyTally>,
+
+    #[account(
+        mut,
+        constraint = task.kind == TaskKind::Bounty { selection: WinnerSelection::DonorVote }
+            @ ApprovalVoteError::WrongApprovalModel
+    )]
+    pub task: Account<'info, Task>,
+
+    /// Leading entry, passed on to task_manager as the winner's entry
+    #[account(
+        seeds = [b"bounty_entry", task.key().as_ref(), bounty_tally.leader.as_ref()],
+        bump = leader_entry.bump,
+        seeds::program = task_manager::ID
+    )]
+    pub leader_entry: Account<'info, BountyEntry>,
+
+    #[account(
+        seeds = [b"escrow", task.key().as_ref()],
+        bump = escrow.bump,
+        seeds::program = task_escrow::ID
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// CHECK: Task history PDA, validated by task_manager
+    #[account(mut)]
+    pub task_history: UncheckedAccount<'info>,
+
+    pub task_manager_program: Program<'info, TaskManager>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event]
+pub struct ApprovalVoteCast {
+    pub task: Pubkey,
+    pub voter: Pubkey,
+    pub approve: bool,
+    pub vote_weight: u64,
+    pub updated: bool,
+    pub voted_at: i64,
+}
+
+#[event]
+pub struct ApprovalFinalized {
+    pub task: Pubkey,
+    pub approved: bool,
+    pub approve_weight: u64,
+    pub reject_weight: u64,
+    pub total_voters: u32,
+    pub finalized_at: i64,
+}
+
+#[event]
+pub struct DeadlineExtensionProposed {
+    pub task: Pubkey,
+    pub recipient: Pubkey,
+    pub current_deadline: i64,
+    pub new_deadline: i64,
+    pub justification_uri: String,
+    pub nonce: u64,
+    pub proposed_at: i64,
+}
+
+#[event]
+pub struct ExtensionVoteCast {
+    pub task: Pubkey,
+    pub voter: Pubkey,
+    pub approve: bool,
+    pub vote_weight: u64,
+    pub updated: bool,
+    pub passed: bool,
+    pub voted_at: i64,
+}
+
+#[event]
+pub struct VetoCast {
+    pub task: Pubkey,
+    pub voter: Pubkey,
+    pub vote_weight: u64,
+    pub total_veto_weight: u64,
+    pub vetoed: bool,
+    pub vetoed_at: i64,
+}
+
+#[event]
+pub struct BountyVoteCast {
+    pub task: Pubkey,
+    pub voter: Pubkey,
+    pub participant: Pubkey,
+    pub vote_weight: u64,
+    pub leader: Pubkey,
+    pub voted_at: i64,
+}
+
+#[event]
+pub struct BountyVoteFinalized {
+    pub task: Pubkey,
+    pub winner: Pubkey,
+    pub winner_weight: u64,
+    pub total_weight: u64,
+    pub total_voters: u32,
+    pub finalized_at: i64,
+}
+
+#[error_code]
+pub enum ApprovalVoteError {
+    #[msg("Contribution too small - minimum $10 USDC required to vote")]
+    ContributionTooSmall,
+
+    #[msg("Quorum not met - need 50% of funds to vote and at least 3 voters")]
+    QuorumNotMet,
+
+    #[msg("Task is not awaiting review")]
+    ReviewNotActive,
+
+    #[msg("Review window has closed")]
+    ReviewWindowClosed,
+
+    #[msg("Review window is still open")]
+    ReviewWindowOpen,
+
+    #[msg("Contribution does not belong to this task and voter")]
+    InvalidContribution,
+
+    #[msg("Task does not use this approval model")]
+    WrongApprovalModel,
+
+    #[msg("Donor veto window is not open")]
+    VetoWindowClosed,
+
+    #[msg("Already vetoed this approval")]
+    AlreadyVetoed,
+
+    #[msg("Only the task recipient can propose a deadline extension")]
+    UnauthorizedRecipient,
+
+    #[msg("Deadline cannot be extended in the current state or after it has passed")]
+    DeadlineNotExtendable,
+
+    #[msg("New deadline must be later than the current deadline")]
+    InvalidDeadline,
+
+    #[msg("Justification URI is empty or too long")]
+    InvalidJustificationUri,
+
+    #[msg("Deadline extension has already been applied")]
+    ExtensionAlreadyExecuted,
+
+    #[msg("Bounty is not open for winner voting")]
+    BountyVotingClosed,
+
+    #[msg("Bounty entry has no submission")]
+    EntryNotSubmitted,
+
+    #[msg("Extension vote is not counted in the current proposal's tally")]
+    ExtensionTallyMismatch,
+}
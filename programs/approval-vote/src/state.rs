@@ -0,0 +1,352 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct ApprovalVote {
+    pub task: Pubkey,
+    pub voter: Pubkey,
+    pub approve: bool,
+    pub vote_weight: u64,  // Contribution amount net of refunds, in USDC
+    pub review_round: i64, // proof_submitted_at of the submission being judged
+    pub voted_at: i64,
+}
+
+impl ApprovalVote {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8;
+}
+
+#[account]
+pub struct ApprovalTally {
+    pub task: Pubkey,
+    pub review_round: i64,  // Resets when the recipient resubmits after a revision
+    pub approve_weight: u64,
+    pub reject_weight: u64,
+    pub total_voters: u32,
+    pub bump: u8,
+}
+
+impl ApprovalTally {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 4 + 1;
+
+    /// Clear the tally for a new submission
+    pub fn reset(&mut self, review_round: i64) {
+        self.review_round = review_round;
+        self.approve_weight = 0;
+        self.reject_weight = 0;
+        self.total_voters = 0;
+    }
+
+    pub fn total_weight(&self) -> u128 {
+        self.approve_weight as u128 + self.reject_weight as u128
+    }
+
+    /// Quorum: enough voters and enough of the net contributed value voted
+    pub fn meets_quorum(&self, net_contributed: u64, quorum_pct: u8, min_voters: u32) -> bool {
+        self.total_voters >= min_voters
+            && net_contributed > 0
+            && self.total_weight() * 100 >= (net_contributed as u128) * quorum_pct as u128
+    }
+
+    /// Threshold: share of voted weight in favour of approval
+    pub fn is_approved(&self, threshold_pct: u8) -> bool {
+        let total = self.total_weight();
+        total > 0 && (self.approve_weight as u128) * 100 >= total * threshold_pct as u128
+    }
+}
+
+/// Donor veto record for a hybrid task's reviewer approval
+#[account]
+pub struct VetoRecord {
+    pub task: Pubkey,
+    pub voter: Pubkey,
+    pub vote_weight: u64,
+    pub veto_round: i64,  // veto_window_ends_at of the approval being vetoed
+    pub vetoed_at: i64,
+}
+
+impl VetoRecord {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8;
+}
+
+#[account]
+pub struct VetoTally {
+    pub task: Pubkey,
+    pub veto_round: i64,  // Resets when a new veto window opens
+    pub veto_weight: u64,
+    pub total_vetoers: u32,
+    pub bump: u8,
+}
+
+impl VetoTally {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 4 + 1;
+
+    /// Clear the tally for a new veto window
+    pub fn reset(&mut self, veto_round: i64) {
+        self.veto_round = veto_round;
+        self.veto_weight = 0;
+        self.total_vetoers = 0;
+    }
+
+    /// Veto passes once vetoing weight exceeds the task's threshold share
+    /// of net contributed value
+    pub fn exceeds_threshold(&self, net_contributed: u64, threshold_bps: u16) -> bool {
+        (self.veto_weight as u128) * 10_000 > (net_contributed as u128) * threshold_bps as u128
+    }
+}
+
+pub const MAX_JUSTIFICATION_URI_LEN: usize = 200;
+
+/// Recipient's request for a later deadline, decided by donor vote
+/// PDA seeds: ["deadline_extension", task]
+#[account]
+pub struct DeadlineExtension {
+    pub task: Pubkey,
+    pub recipient: Pubkey,
+    pub new_deadline: i64,
+    pub justification_uri: String,  // IPFS/Arweave
+    pub proposed_at: i64,
+    pub nonce: u64,                 // Incremented per proposal; identifies the round for votes
+    pub approve_weight: u64,
+    pub reject_weight: u64,
+    pub total_voters: u32,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+impl DeadlineExtension {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + (4 + MAX_JUSTIFICATION_URI_LEN) + 8 + 8 + 8 + 8 + 4 + 1 + 1;
+
+    /// Add a vote's weight to its side
+    pub fn add_vote(&mut self, approve: bool, weight: u64) -> Option<()> {
+        let side = if approve { &mut self.approve_weight } else { &mut self.reject_weight };
+        *side = side.checked_add(weight)?;
+        Some(())
+    }
+
+    /// Take back a vote counted in this round; None if the tally doesn't hold it
+    pub fn remove_vote(&mut self, approve: bool, weight: u64) -> Option<()> {
+        let side = if approve { &mut self.approve_weight } else { &mut self.reject_weight };
+        *side = side.checked_sub(weight)?;
+        Some(())
+    }
+
+    /// Passes once approving weight exceeds the threshold share of net contributed value
+    pub fn passes(&self, net_contributed: u64, threshold_pct: u8) -> bool {
+        (self.approve_weight as u128) * 100 > (net_contributed as u128) * threshold_pct as u128
+    }
+}
+
+#[account]
+pub struct ExtensionVote {
+    pub task: Pubkey,
+    pub voter: Pubkey,
+    pub approve: bool,
+    pub vote_weight: u64,
+    pub proposal_nonce: u64,  // nonce of the proposal voted on
+    pub voted_at: i64,
+}
+
+impl ExtensionVote {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8;
+}
+
+/// Donor vote for a bounty's winner
+/// PDA seeds: ["bounty_tally", task]
+#[account]
+pub struct BountyTally {
+    pub task: Pubkey,
+    pub leader: Pubkey,      // Participant with the most weight so far
+    pub leader_weight: u64,
+    pub total_weight: u64,
+    pub total_voters: u32,
+    pub bump: u8,
+}
+
+impl BountyTally {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 4 + 1;
+
+    /// Add a vote for a candidate and update the leader.
+    /// Votes are final, so weights only grow and the leader is always the
+    /// top candidate; on a tie the candidate that reached it first leads.
+    pub fn record_vote(&mut self, candidate: &mut BountyCandidate, vote_weight: u64) {
+        candidate.weight = candidate.weight.checked_add(vote_weight).unwrap();
+        self.total_weight = self.total_weight.checked_add(vote_weight).unwrap();
+        self.total_voters += 1;
+
+        if candidate.weight > self.leader_weight {
+            self.leader = candidate.participant;
+            self.leader_weight = candidate.weight;
+        }
+    }
+
+    /// Quorum: enough voters and enough of the net contributed value voted
+    pub fn meets_quorum(&self, net_contributed: u64, quorum_pct: u8, min_voters: u32) -> bool {
+        self.total_voters >= min_voters
+            && net_contributed > 0
+            && (self.total_weight as u128) * 100 >= (net_contributed as u128) * quorum_pct as u128
+    }
+}
+
+/// Weight behind one bounty entry
+/// PDA seeds: ["bounty_candidate", task, participant]
+#[account]
+pub struct BountyCandidate {
+    pub task: Pubkey,
+    pub participant: Pubkey,
+    pub weight: u64,
+    pub bump: u8,
+}
+
+impl BountyCandidate {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+/// PDA seeds: ["bounty_vote", task, voter]
+#[account]
+pub struct BountyVote {
+    pub task: Pubkey,
+    pub voter: Pubkey,
+    pub participant: Pubkey,
+    pub vote_weight: u64,
+    pub voted_at: i64,
+}
+
+impl BountyVote {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tally(approve_weight: u64, reject_weight: u64, total_voters: u32) -> ApprovalTally {
+        ApprovalTally {
+            task: Pubkey::default(),
+            review_round: 0,
+            approve_weight,
+            reject_weight,
+            total_voters,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_quorum_by_value_and_voters() {
+        // 60 of 100 voted by 3 voters
+        assert!(tally(40, 20, 3).meets_quorum(100, 50, 3));
+        // Not enough value
+        assert!(!tally(30, 10, 3).meets_quorum(100, 50, 3));
+        // Not enough voters
+        assert!(!tally(40, 20, 2).meets_quorum(100, 50, 3));
+        // Nothing contributed
+        assert!(!tally(0, 0, 3).meets_quorum(0, 50, 3));
+    }
+
+    #[test]
+    fn test_approval_threshold() {
+        assert!(tally(60, 40, 3).is_approved(60));
+        assert!(!tally(59, 41, 3).is_approved(60));
+        assert!(!tally(0, 0, 0).is_approved(60));
+    }
+
+    #[test]
+    fn test_veto_threshold_is_strict() {
+        let mut veto = VetoTally {
+            task: Pubkey::default(),
+            veto_round: 0,
+            veto_weight: 3_000,
+            total_vetoers: 1,
+            bump: 0,
+        };
+
+        // Exactly 30% does not pass a 30% threshold; more than 30% does
+        assert!(!veto.exceeds_threshold(10_000, 3_000));
+        veto.veto_weight = 3_001;
+        assert!(veto.exceeds_threshold(10_000, 3_000));
+    }
+
+    #[test]
+    fn test_extension_needs_majority_of_contributed_value() {
+        let mut extension = DeadlineExtension {
+            task: Pubkey::default(),
+            recipient: Pubkey::default(),
+            new_deadline: 0,
+            justification_uri: String::new(),
+            proposed_at: 0,
+            nonce: 1,
+            approve_weight: 50,
+            reject_weight: 0,
+            total_voters: 1,
+            executed: false,
+            bump: 0,
+        };
+
+        assert!(!extension.passes(100, 50));
+        extension.approve_weight = 51;
+        assert!(extension.passes(100, 50));
+    }
+
+    #[test]
+    fn test_extension_vote_changes() {
+        let mut extension = DeadlineExtension {
+            task: Pubkey::default(),
+            recipient: Pubkey::default(),
+            new_deadline: 0,
+            justification_uri: String::new(),
+            proposed_at: 0,
+            nonce: 1,
+            approve_weight: 0,
+            reject_weight: 0,
+            total_voters: 0,
+            executed: false,
+            bump: 0,
+        };
+
+        extension.add_vote(false, 40).unwrap();
+        extension.remove_vote(false, 40).unwrap();
+        extension.add_vote(true, 40).unwrap();
+        assert_eq!((extension.approve_weight, extension.reject_weight), (40, 0));
+
+        // A vote from an earlier proposal was never counted here
+        assert!(extension.remove_vote(false, 40).is_none());
+        assert!(extension.remove_vote(true, 41).is_none());
+        assert_eq!((extension.approve_weight, extension.reject_weight), (40, 0));
+    }
+
+    #[test]
+    fn test_no_overflow_on_large_weights() {
+        let t = tally(u64::MAX, u64::MAX / 2, 3);
+        assert!(t.meets_quorum(u64::MAX, 50, 3));
+    }
+
+    fn candidate(participant: Pubkey) -> BountyCandidate {
+        BountyCandidate { task: Pubkey::default(), participant, weight: 0, bump: 0 }
+    }
+
+    #[test]
+    fn test_bounty_leader_tracking() {
+        let mut tally = BountyTally {
+            task: Pubkey::default(),
+            leader: Pubkey::default(),
+            leader_weight: 0,
+            total_weight: 0,
+            total_voters: 0,
+            bump: 0,
+        };
+        let mut a = candidate(Pubkey::new_unique());
+        let mut b = candidate(Pubkey::new_unique());
+
+        tally.record_vote(&mut a, 30);
+        tally.record_vote(&mut b, 30);
+        assert_eq!(tally.leader, a.participant); // tie: first to reach leads
+
+        tally.record_vote(&mut b, 1);
+        assert_eq!(tally.leader, b.participant);
+        assert_eq!(tally.leader_weight, 31);
+        assert_eq!(tally.total_weight, 61);
+        assert_eq!(tally.total_voters, 3);
+
+        assert!(tally.meets_quorum(100, 50, 3));
+        assert!(!tally.meets_quorum(200, 50, 3));
+        assert!(!tally.meets_quorum(100, 50, 4));
+    }
+}
@@ -0,0 +1,515 @@
+/// Weighted Median Algorithm
+///
+/// ANTI-SYBIL MECHANISM:
+/// Traditional median: Each vote counts equally → Sybil can spam votes
+/// Weighted median: Each vote weighted by contribution → Sybil must spend real money
+///
+/// Example:
+/// - Attacker: 100 wallets × $10 = $1000 total, each voting for $100 budget
+/// - Legitimate donor: 1 wallet × $1000, voting for $500 budget
+/// - Traditional median: $100 (attacker wins with 100 votes vs 1)
+/// - Weighted median: $500 (both have equal weight of $1000)
+///
+/// Algorithm:
+/// 1. Sort all votes by proposed_budget
+/// 2. Find the lowest budget where cumulative weight reaches 50% of total weight
+///    (2 × cumulative ≥ total, so odd totals aren't rounded down)
+/// 3. Tie: when cumulative weight lands exactly on half, the lower of the two
+///    neighbouring budgets wins (the conservative choice for donors' funds)
+///
+/// Weight sums are u128 throughout. With a total weight that fits in u64 (as
+/// the aggregate enforces), budget × weight products fit in u128 as well.
+///
+/// Other methods a task can choose when budget voting starts:
+/// - Interpolated median: weighted median placed between neighbouring budgets
+///   by how far the halfway weight sits between them
+/// - Trimmed mean: weighted mean after dropping a share of the weight from
+///   each end, so a few extreme proposals can't drag the average
+/// - Capped median: weighted median with every voter's weight capped at a
+///   share of the total, so one whale can't decide alone
+
+use anchor_lang::prelude::*;
+pub use task_manager::state::BudgetAggregation;
+
+/// Basis points in 100%
+const BPS: u128 = 10_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct WeightedVote {
+    pub proposed_budget: u64,
+    pub weight: u64,
+}
+
+/// Calculate weighted median from votes
+/// Zero-weight votes are ignored; with no weight at all the result is 0.
+pub fn calculate_weighted_median(mut votes: Vec<WeightedVote>) -> u64 {
+    votes.retain(|v| v.weight > 0);
+    if votes.is_empty() {
+        return 0;
+    }
+
+    // Sort by proposed_budget ascending
+    votes.sort_by_key(|v| v.proposed_budget);
+
+    // Calculate total weight
+    let total_weight: u128 = votes.iter().map(|v| v.weight as u128).sum();
+
+    // Find median: first budget whose cumulative weight reaches half
+    let mut cumulative_weight = 0u128;
+    for vote in &votes {
+        cumulative_weight += vote.weight as u128;
+        if 2 * cumulative_weight >= total_weight {
+            return vote.proposed_budget;
+        }
+    }
+
+    // Fallback (should never reach here if votes non-empty)
+    votes.last().unwrap().proposed_budget
+}
+
+/// Aggregate votes with the task's chosen method
+pub fn aggregate_budget(method: &BudgetAggregation, votes: Vec<WeightedVote>) -> u64 {
+    match method {
+        BudgetAggregation::WeightedMedian => calculate_weighted_median(votes),
+        BudgetAggregation::InterpolatedMedian => calculate_interpolated_median(votes),
+        BudgetAggregation::TrimmedMean { trim_bps } => calculate_trimmed_mean(votes, *trim_bps),
+        BudgetAggregation::CappedMedian { cap_bps } => calculate_capped_median(votes, *cap_bps),
+    }
+}
+
+/// Weighted median interpolated between neighbouring budgets
+/// Each vote sits at the midpoint of its weight on the cumulative scale; the
+/// result is read off the line between the two votes around half the total.
+/// Where the plain median breaks an exact-half tie towards the lower budget,
+/// this lands between the two; equal weights give the classic median (the
+/// mean of the middle two, rounded down, for an even count).
+pub fn calculate_interpolated_median(mut votes: Vec<WeightedVote>) -> u64 {
+    votes.retain(|v| v.weight > 0);
+    if votes.is_empty() {
+        return 0;
+    }
+    votes.sort_by_key(|v| v.proposed_budget);
+
+    // Positions are doubled to stay in integers: 2 * cumulative-before + weight
+    let total: u128 = votes.iter().map(|v| v.weight as u128).sum();
+    let mut before = 0u128;
+    let mut previous: Option<(u128, u64)> = None;
+
+    for vote in &votes {
+        let position = 2 * before + vote.weight as u128;
+        if position >= total {
+            return match previous {
+                None => vote.proposed_budget,
+                Some((prev_position, prev_budget)) => {
+                    let span = (vote.proposed_budget - prev_budget) as u128;
+                    let offset = span * (total - prev_position) / (position - prev_position);
+                    prev_budget + offset as u64
+                }
+            };
+        }
+        previous = Some((position, vote.proposed_budget));
+        before += vote.weight as u128;
+    }
+
+    votes.last().unwrap().proposed_budget
+}
+
+/// Weighted mean after dropping `trim_bps` of the total weight from each end
+/// Votes straddling a cut count with the part of their weight that remains.
+pub fn calculate_trimmed_mean(mut votes: Vec<WeightedVote>, trim_bps: u16) -> u64 {
+    if votes.is_empty() {
+        return 0;
+    }
+    votes.sort_by_key(|v| v.proposed_budget);
+
+    let total: u128 = votes.iter().map(|v| v.weight as u128).sum();
+    let trim = total * trim_bps as u128 / BPS;
+    let (low, high) = (trim, total - trim);
+
+    let mut before = 0u128;
+    let mut kept_weight = 0u128;
+    let mut weighted_sum = 0u128;
+    for vote in &votes {
+        let start = before;
+        let end = before + vote.weight as u128;
+        before = end;
+
+        let kept = end.min(high).saturating_sub(start.max(low));
+        kept_weight += kept;
+        weighted_sum += vote.proposed_budget as u128 * kept;
+    }
+
+    if kept_weight == 0 {
+        return calculate_weighted_median(votes);
+    }
+    (weighted_sum / kept_weight) as u64
+}
+
+/// Weighted median with each voter's weight capped at `cap_bps` of the total
+pub fn calculate_capped_median(votes: Vec<WeightedVote>, cap_bps: u16) -> u64 {
+    let total: u128 = votes.iter().map(|v| v.weight as u128).sum();
+    let cap = (total * cap_bps as u128 / BPS).max(1) as u64;
+
+    let capped = votes
+        .into_iter()
+        .map(|v| WeightedVote {
+            proposed_budget: v.proposed_budget,
+            weight: v.weight.min(cap),
+        })
+        .collect();
+    calculate_weighted_median(capped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn votes(entries: &[(u64, u64)]) -> Vec<WeightedVote> {
+        entries
+            .iter()
+            .map(|&(proposed_budget, weight)| WeightedVote { proposed_budget, weight })
+            .collect()
+    }
+
+    /// 10 attacker wallets × $1 voting $50, one $100 donor voting $200
+    fn sybil_votes() -> Vec<WeightedVote> {
+        let mut entries = vec![(50, 1); 10];
+        entries.push((200, 100));
+        votes(&entries)
+    }
+
+    #[test]
+    fn test_weighted_median_equal_weights() {
+        let votes = vec![
+            WeightedVote { proposed_budget: 100, weight: 10 },
+            WeightedVote { proposed_budget: 200, weight: 10 },
+            WeightedVote { proposed_budget: 300, weight: 10 },
+        ];
+
+        let median = calculate_weighted_median(votes);
+        assert_eq!(median, 200);
+    }
+
+    #[test]
+    fn test_weighted_median_unequal_weights() {
+        // Sybil attack scenario
+        let votes = vec![
+            // Attacker: 10 votes × $1 each = $10 total weight, all voting $50
+            WeightedVote { proposed_budget: 50, weight: 1 },
+            WeightedVote { proposed_budget: 50, weight: 1 },
+            WeightedVote { proposed_budget: 50, weight: 1 },
+            WeightedVote { proposed_budget: 50, weight: 1 },
+            WeightedVote { proposed_budget: 50, weight: 1 },
+            WeightedVote { proposed_budget: 50, weight: 1 },
+            WeightedVote { proposed_budget: 50, weight: 1 },
+            WeightedVote { proposed_budget: 50, weight: 1 },
+            WeightedVote { proposed_budget: 50, weight: 1 },
+            WeightedVote { proposed_budget: 50, weight: 1 },
+            // Legitimate donor: 1 vote × $100 weight, voting $200
+            WeightedVote { proposed_budget: 200, weight: 100 },
+        ];
+
+        let median = calculate_weighted_median(votes);
+        // Despite 10 attacker votes vs 1 legitimate vote,
+        // median should be $200 because legitimate donor has more weight ($100 > $10)
+        assert_eq!(median, 200);
+    }
+
+    #[test]
+    fn test_weighted_median_resistance_to_outliers() {
+        let votes = vec![
+            WeightedVote { proposed_budget: 100, weight: 50 },
+            WeightedVote { proposed_budget: 150, weight: 50 },
+            WeightedVote { proposed_budget: 200, weight: 50 },
+            // Outlier with low weight
+            WeightedVote { proposed_budget: 10000, weight: 1 },
+        ];
+
+        let median = calculate_weighted_median(votes);
+        // Median should be ~150-200, not influenced by extreme outlier
+        assert!(median >= 150 && median <= 200);
+    }
+
+    #[test]
+    fn test_single_vote() {
+        let votes = vec![
+            WeightedVote { proposed_budget: 500, weight: 100 },
+        ];
+
+        let median = calculate_weighted_median(votes);
+        assert_eq!(median, 500);
+    }
+
+    #[test]
+    fn test_odd_total_not_biased_low() {
+        // 200 holds 2/3 of the weight; halving 3 down to 1 used to pick 100
+        assert_eq!(calculate_weighted_median(votes(&[(100, 1), (200, 2)])), 200);
+        assert_eq!(calculate_weighted_median(votes(&[(100, 2), (200, 1)])), 100);
+    }
+
+    #[test]
+    fn test_exact_half_tie() {
+        // Cumulative weight lands exactly on half after 100: lower budget wins
+        let entries = votes(&[(100, 5), (200, 5)]);
+        assert_eq!(calculate_weighted_median(entries.clone()), 100);
+        assert_eq!(calculate_interpolated_median(entries), 150);
+
+        let entries = votes(&[(100, 3), (150, 2), (300, 5)]);
+        assert_eq!(calculate_weighted_median(entries), 150);
+    }
+
+    #[test]
+    fn test_zero_weights() {
+        assert_eq!(calculate_weighted_median(votes(&[(100, 0), (200, 0)])), 0);
+        assert_eq!(calculate_weighted_median(votes(&[(100, 0), (200, 1)])), 200);
+    }
+
+    #[test]
+    fn test_large_weights_do_not_overflow() {
+        let whale = u64::MAX / 4;
+        let entries = votes(&[(100, whale), (200, whale), (300, whale), (u64::MAX, whale)]);
+        assert_eq!(calculate_weighted_median(entries.clone()), 200);
+        assert_eq!(calculate_interpolated_median(entries.clone()), 250);
+        assert_eq!(calculate_trimmed_mean(entries.clone(), 2_500), 250);
+        assert_eq!(calculate_capped_median(entries, 2_500), 200);
+    }
+
+    #[test]
+    fn test_aggregate_dispatch() {
+        let entries = votes(&[(100, 10), (200, 10), (300, 10)]);
+        let methods = [
+            BudgetAggregation::WeightedMedian,
+            BudgetAggregation::InterpolatedMedian,
+            BudgetAggregation::TrimmedMean { trim_bps: 2_000 },
+            BudgetAggregation::CappedMedian { cap_bps: 5_000 },
+        ];
+        for method in &methods {
+            assert_eq!(aggregate_budget(method, entries.clone()), 200);
+            assert_eq!(aggregate_budget(method, vec![]), 0);
+        }
+    }
+
+    #[test]
+    fn test_interpolated_median() {
+        // Halfway weight sits exactly between the two votes
+        assert_eq!(calculate_interpolated_median(votes(&[(100, 10), (200, 10)])), 150);
+
+        // Heavier 200 vote pulls the result towards it
+        assert_eq!(calculate_interpolated_median(votes(&[(100, 10), (200, 30)])), 175);
+
+        // Zero-weight votes are ignored
+        assert_eq!(calculate_interpolated_median(votes(&[(0, 0), (100, 10), (200, 10)])), 150);
+        assert_eq!(calculate_interpolated_median(votes(&[(500, 100)])), 500);
+    }
+
+    #[test]
+    fn test_interpolated_median_sybil() {
+        // Attacker weight barely moves the result off the donor's budget
+        let median = calculate_interpolated_median(sybil_votes());
+        assert!((180..=200).contains(&median));
+    }
+
+    #[test]
+    fn test_trimmed_mean() {
+        let entries = votes(&[(100, 10), (200, 10), (300, 10), (10_000, 10)]);
+
+        // No trim: plain weighted mean
+        assert_eq!(calculate_trimmed_mean(entries.clone(), 0), 2_650);
+
+        // 25% off each end drops the outlier and the lowest vote
+        assert_eq!(calculate_trimmed_mean(entries.clone(), 2_500), 250);
+
+        // 30% off each end cuts into the middle votes' weight too
+        assert_eq!(calculate_trimmed_mean(entries, 3_000), 250);
+    }
+
+    #[test]
+    fn test_trimmed_mean_sybil() {
+        // Cheap wallets at the low end are trimmed away first
+        assert_eq!(calculate_trimmed_mean(sybil_votes(), 1_000), 200);
+    }
+
+    #[test]
+    fn test_capped_median() {
+        // Whale with 70% of weight capped at 25%: the rest decide
+        let entries = votes(&[(100, 10), (200, 10), (300, 10), (1_000, 70)]);
+        assert_eq!(calculate_weighted_median(entries.clone()), 1_000);
+        assert_eq!(calculate_capped_median(entries.clone(), 2_500), 300);
+
+        // A 100% cap changes nothing
+        assert_eq!(calculate_capped_median(entries, 10_000), 1_000);
+    }
+
+    #[test]
+    fn test_capped_median_sybil() {
+        // The cap bounds each voter, not the count of wallets, so a donor
+        // still outweighs cheap wallets as long as the cap exceeds their size
+        assert_eq!(calculate_capped_median(sybil_votes(), 5_000), 200);
+    }
+
+    /// Reference: expand every vote into `weight` unit votes and take the
+    /// lower middle element
+    fn reference_unit_median(votes: &[WeightedVote]) -> u64 {
+        let mut units: Vec<u64> = votes
+            .iter()
+            .flat_map(|v| std::iter::repeat_n(v.proposed_budget, v.weight as usize))
+            .collect();
+        if units.is_empty() {
+            return 0;
+        }
+        units.sort_unstable();
+        units[(units.len() - 1) / 2]
+    }
+
+    /// Reference: the lowest proposed budget with at least as much weight at
+    /// or below it as above it, checked candidate by candidate
+    fn reference_median(votes: &[WeightedVote]) -> u64 {
+        let mut candidates: Vec<u64> = votes
+            .iter()
+            .filter(|v| v.weight > 0)
+            .map(|v| v.proposed_budget)
+            .collect();
+        candidates.sort_unstable();
+        for candidate in candidates {
+            let at_or_below: u128 = votes
+                .iter()
+                .filter(|v| v.proposed_budget <= candidate)
+                .map(|v| v.weight as u128)
+                .sum();
+            let above: u128 = votes
+                .iter()
+                .filter(|v| v.proposed_budget > candidate)
+                .map(|v| v.weight as u128)
+                .sum();
+            if at_or_below >= above {
+                return candidate;
+            }
+        }
+        0
+    }
+
+    /// Reference: the classic median of equally weighted votes
+    fn reference_classic_median(mut budgets: Vec<u64>) -> u64 {
+        budgets.sort_unstable();
+        let n = budgets.len();
+        if n % 2 == 1 {
+            budgets[n / 2]
+        } else {
+            ((budgets[n / 2 - 1] as u128 + budgets[n / 2] as u128) / 2) as u64
+        }
+    }
+
+    fn vote_sets() -> impl Strategy<Value = Vec<WeightedVote>> {
+        prop::collection::vec((0u64..1_000_000, 1u64..1_000_000_000), 1..50)
+            .prop_map(|entries| votes(&entries))
+    }
+
+    fn bounds(votes: &[WeightedVote]) -> (u64, u64) {
+        let min = votes.iter().map(|v| v.proposed_budget).min().unwrap();
+        let max = votes.iter().map(|v| v.proposed_budget).max().unwrap();
+        (min, max)
+    }
+
+    proptest! {
+        /// Every method lands within the range of proposed budgets
+        #[test]
+        fn prop_result_within_proposals(
+            entries in vote_sets(),
+            trim_bps in 0u16..5_000,
+            cap_bps in 1u16..=10_000,
+        ) {
+            let (min, max) = bounds(&entries);
+            for method in [
+                BudgetAggregation::WeightedMedian,
+                BudgetAggregation::InterpolatedMedian,
+                BudgetAggregation::TrimmedMean { trim_bps },
+                BudgetAggregation::CappedMedian { cap_bps },
+            ] {
+                let result = aggregate_budget(&method, entries.clone());
+                prop_assert!(result >= min && result <= max, "{:?} gave {}", method, result);
+            }
+        }
+
+        /// Vote order never matters
+        #[test]
+        fn prop_order_independent(entries in vote_sets(), trim_bps in 0u16..5_000, cap_bps in 1u16..=10_000) {
+            let mut reversed = entries.clone();
+            reversed.reverse();
+            prop_assert_eq!(calculate_interpolated_median(entries.clone()), calculate_interpolated_median(reversed.clone()));
+            prop_assert_eq!(calculate_trimmed_mean(entries.clone(), trim_bps), calculate_trimmed_mean(reversed.clone(), trim_bps));
+            prop_assert_eq!(calculate_capped_median(entries.clone(), cap_bps), calculate_capped_median(reversed, cap_bps));
+        }
+
+        /// Unanimous votes give that budget under every method
+        #[test]
+        fn prop_unanimous(budget in 0u64..1_000_000, weights in prop::collection::vec(1u64..1_000_000_000, 1..20), trim_bps in 0u16..5_000, cap_bps in 1u16..=10_000) {
+            let entries: Vec<WeightedVote> = weights
+                .into_iter()
+                .map(|weight| WeightedVote { proposed_budget: budget, weight })
+                .collect();
+            prop_assert_eq!(calculate_interpolated_median(entries.clone()), budget);
+            prop_assert_eq!(calculate_trimmed_mean(entries.clone(), trim_bps), budget);
+            prop_assert_eq!(calculate_capped_median(entries, cap_bps), budget);
+        }
+
+        /// Small weights: matches the median of the unit-vote expansion
+        #[test]
+        fn prop_median_matches_unit_reference(
+            entries in prop::collection::vec((0u64..50, 0u64..20), 1..30)
+        ) {
+            let entries = votes(&entries);
+            prop_assert_eq!(calculate_weighted_median(entries.clone()), reference_unit_median(&entries));
+        }
+
+        /// Weights up to u64::MAX / 64: matches the naive reference without overflowing
+        #[test]
+        fn prop_median_matches_reference(
+            entries in prop::collection::vec((any::<u64>(), 0u64..u64::MAX / 64), 1..60)
+        ) {
+            let entries = votes(&entries);
+            prop_assert_eq!(calculate_weighted_median(entries.clone()), reference_median(&entries));
+        }
+
+        /// Equal weights: the interpolated median is the classic median
+        #[test]
+        fn prop_interpolated_equal_weights(
+            budgets in prop::collection::vec(any::<u64>(), 1..60),
+            weight in 1u64..u64::MAX / 64,
+        ) {
+            let entries: Vec<WeightedVote> = budgets
+                .iter()
+                .map(|&proposed_budget| WeightedVote { proposed_budget, weight })
+                .collect();
+            prop_assert_eq!(calculate_interpolated_median(entries), reference_classic_median(budgets));
+        }
+
+        /// The interpolated median stays between the weighted median's neighbours
+        #[test]
+        fn prop_interpolated_brackets_median(
+            entries in prop::collection::vec((any::<u64>(), 1u64..u64::MAX / 64), 1..60)
+        ) {
+            let entries = votes(&entries);
+            let mut budgets: Vec<u64> = entries.iter().map(|v| v.proposed_budget).collect();
+            budgets.sort_unstable();
+
+            let median = calculate_weighted_median(entries.clone());
+            let first = budgets.iter().position(|&b| b == median).unwrap();
+            let last = budgets.iter().rposition(|&b| b == median).unwrap();
+            let low = budgets[first.saturating_sub(1)];
+            let high = budgets[(last + 1).min(budgets.len() - 1)];
+            let interpolated = calculate_interpolated_median(entries);
+            prop_assert!(interpolated >= low && interpolated <= high, "{} outside {}..={}", interpolated, low, high);
+        }
+
+        /// Scaling every weight by the same factor leaves the interpolated median unchanged
+        #[test]
+        fn prop_interpolated_scale_invariant(entries in vote_sets(), factor in 1u64..1_000) {
+            let scaled: Vec<WeightedVote> = entries
+                .iter()
+                .map(|v| WeightedVote { proposed_budget: v.proposed_budget, weight: v.weight * factor })
+                .collect();
+            prop_assert_eq!(calculate_interpolated_median(entries), calculate_interpolated_median(scaled));
+        }
+    }
+}
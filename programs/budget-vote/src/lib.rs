@@ -1,19 +1,27 @@
 use anchor_lang::prelude::*;
+use campaign_registry::state::Campaign;
+use task_escrow::state::{Contribution, Escrow};
+use task_manager::cpi::accounts::FinalizeBudget as TaskFinalizeBudget;
+use task_manager::program::TaskManager;
+use task_manager::state::{BudgetBallot, Task, TaskState};
 
 pub mod state;
-pub mod weighted_median;
+pub mod aggregation;
 
 use state::*;
-use weighted_median::*;
+use aggregation::*;
 
 declare_id!("Budg1111111111111111111111111111111111111111");
 
 /// Minimum contribution to have voting power: $10 USDC (6 decimals)
 pub const MIN_CONTRIBUTION_FOR_VOTE: u64 = 10_000_000;
 
-/// Quorum: 60% of total contribution value must vote
+/// Quorum: 60% of the contribution value snapshotted when voting started must vote
 pub const QUORUM_PERCENTAGE: u8 = 60;
 
+/// Early finalize: 80% of total contribution value has voted before the window closes
+pub const EARLY_FINALIZE_PERCENTAGE: u8 = 80;
+
 /// Minimum unique voters required
 pub const MIN_VOTERS: u32 = 3;
 
@@ -21,103 +29,513 @@ pub const MIN_VOTERS: u32 = 3;
 pub mod budget_vote {
     use super::*;
 
+    /// Open the task's vote tally once budget voting has started
+    /// Anyone can pay for it; the account starts empty and each new voter
+    /// pays to grow it by one entry
+    pub fn open_budget_aggregate(ctx: Context<OpenBudgetAggregate>) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let aggregate = &mut ctx.accounts.budget_aggregate;
+        aggregate.task = task.key();
+        aggregate.voting_ends_at = task
+            .budget_voting_ends_at()
+            .ok_or(BudgetVoteError::VotingNotActive)?;
+        Ok(())
+    }
+
     /// Submit or update budget vote
-    /// Vote weight = contributor's total contribution to this task (USDC amount, not per-wallet)
-    pub fn submit_vote(
-        ctx: Context<SubmitVote>,
-        proposed_budget: u64,
-        contribution_amount: u64,
-    ) -> Result<()> {
+    /// Vote weight = contributor's contribution to this task when budget voting
+    /// started (USDC amount, not per-wallet), less earlier refunds, read from
+    /// the snapshot on the task_escrow Contribution receipt, plus any weight
+    /// delegated to the voter. A delegate without a contribution of their own
+    /// votes with no contribution account and carries delegated weight only.
+    pub fn submit_vote(ctx: Context<SubmitVote>, proposed_budget: u64) -> Result<()> {
+        let contribution_amount = match &ctx.accounts.contribution {
+            Some(contribution) => {
+                let weight = contribution.snapshot_weight();
+                require!(
+                    weight >= MIN_CONTRIBUTION_FOR_VOTE,
+                    BudgetVoteError::ContributionTooSmall
+                );
+                weight
+            }
+            None => 0,
+        };
+
+        let task = &ctx.accounts.task;
         require!(
-            contribution_amount >= MIN_CONTRIBUTION_FOR_VOTE,
-            BudgetVoteError::ContributionTooSmall
+            task.budget_ballot == BudgetBallot::Open,
+            BudgetVoteError::CommitRevealBallot
         );
+        let clock = Clock::get()?;
+        let voting_ends_at = task
+            .budget_voting_ends_at()
+            .ok_or(BudgetVoteError::VotingNotActive)?;
+        require!(
+            clock.unix_timestamp <= voting_ends_at,
+            BudgetVoteError::VotingNotActive
+        );
+
+        // Out-of-range votes are rejected or clamped, per the task's bounds
+        let bounded_budget = task
+            .bounded_budget(proposed_budget)
+            .ok_or(BudgetVoteError::BudgetOutOfRange)?;
+        let clamped = bounded_budget != proposed_budget;
+        let proposed_budget = bounded_budget;
 
         let vote = &mut ctx.accounts.budget_vote;
-        let clock = Clock::get()?;
+        let aggregate = &mut ctx.accounts.budget_aggregate;
+        let updated = vote.voter != Pubkey::default();
+
+        // Voting directly overrides a vote a delegate cast for this voter
+        release_delegated_vote(vote, ctx.accounts.previous_delegate_vote.as_deref_mut(), aggregate)?;
+
+        // A re-vote replaces this voter's previous entry; weight delegated to
+        // them moves to the new budget with it
+        let previous = vote.tally_entry();
 
         vote.task = ctx.accounts.task.key();
         vote.voter = ctx.accounts.voter.key();
         vote.proposed_budget = proposed_budget;
         vote.vote_weight = contribution_amount; // ANTI-SYBIL: Weight by USDC, not wallet count
         vote.voted_at = clock.unix_timestamp;
+        vote.delegated_by = None;
 
-        // Update aggregate
-        let aggregate = &mut ctx.accounts.budget_aggregate;
-        if aggregate.task == Pubkey::default() {
-            // Initialize
-            aggregate.task = ctx.accounts.task.key();
-            aggregate.total_voters = 1;
-            aggregate.total_weight = contribution_amount;
-        } else {
-            // Check if new voter
-            let is_new_voter = true; // In production, check if voter exists in votes list
-            if is_new_voter {
-                aggregate.total_voters += 1;
-            }
-            aggregate.total_weight += contribution_amount;
-        }
+        aggregate.replace_vote(previous.as_ref(), vote.tally_entry().as_ref())?;
 
         emit!(BudgetVoteSubmitted {
             task: ctx.accounts.task.key(),
             voter: ctx.accounts.voter.key(),
             proposed_budget,
             vote_weight: contribution_amount,
+            updated,
+            clamped,
             voted_at: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Finalize budget using weighted median
-    /// Can only be called when quorum is met
-    pub fn finalize_budget(
-        ctx: Context<FinalizeBudget>,
-        total_contributed: u64,
+    /// Delegate budget vote weight for one task or a whole campaign
+    /// A task delegation takes precedence over a campaign delegation;
+    /// delegating again for the same target replaces the delegate
+    pub fn delegate_budget_vote(
+        ctx: Context<DelegateBudgetVote>,
+        scope: DelegationScope,
+        delegate: Pubkey,
     ) -> Result<()> {
-        let aggregate = &ctx.accounts.budget_aggregate;
+        let delegator = ctx.accounts.delegator.key();
+        require_keys_neq!(delegate, delegator, BudgetVoteError::SelfDelegation);
+
+        let target = &ctx.accounts.target;
+        let valid_target = match scope {
+            DelegationScope::Task => holds_account::<Task>(target),
+            DelegationScope::Campaign => holds_account::<Campaign>(target),
+        };
+        require!(valid_target, BudgetVoteError::InvalidDelegationTarget);
+
+        let delegation = &mut ctx.accounts.delegation;
+        let clock = Clock::get()?;
+        let updated = delegation.delegator != Pubkey::default();
+
+        delegation.delegator = delegator;
+        delegation.delegate = delegate;
+        delegation.scope = scope.clone();
+        delegation.target = ctx.accounts.target.key();
+        delegation.delegated_at = clock.unix_timestamp;
+        delegation.bump = ctx.bumps.delegation;
+
+        emit!(BudgetVoteDelegated {
+            delegator,
+            delegate,
+            scope,
+            target: delegation.target,
+            updated,
+            delegated_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Revoke a delegation
+    /// Passing the delegator's vote, the delegate's vote and the aggregate of
+    /// a task whose ballot is still open takes the vote the delegate cast
+    /// there back out of the tally. For other tasks a campaign delegation
+    /// reached, a direct vote (with or without weight) replaces it.
+    pub fn revoke_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+        let delegation = &ctx.accounts.delegation;
+        let clock = Clock::get()?;
+
+        let mut withdrawn_weight = 0;
+        if let (Some(vote), Some(delegate_vote), Some(aggregate)) = (
+            ctx.accounts.budget_vote.as_deref_mut(),
+            ctx.accounts.delegate_vote.as_deref_mut(),
+            ctx.accounts.budget_aggregate.as_deref_mut(),
+        ) {
+            require_keys_eq!(vote.voter, delegation.delegator, BudgetVoteError::InvalidDelegation);
+            require_keys_eq!(vote.task, aggregate.task, BudgetVoteError::InvalidDelegation);
+
+            if vote.delegated_by == Some(delegation.delegate)
+                && clock.unix_timestamp <= aggregate.voting_ends_at
+            {
+                release_delegated_vote(vote, Some(delegate_vote), aggregate)?;
+                withdrawn_weight = vote.vote_weight;
+
+                // Back to not having voted: free to vote directly or through
+                // another delegate
+                vote.voter = Pubkey::default();
+                vote.proposed_budget = 0;
+                vote.vote_weight = 0;
+                vote.delegated_by = None;
+            }
+        }
+
+        emit!(BudgetDelegationRevoked {
+            delegator: delegation.delegator,
+            delegate: delegation.delegate,
+            scope: delegation.scope.clone(),
+            target: delegation.target,
+            withdrawn_weight,
+            revoked_at: clock.unix_timestamp,
+        });
 
-        // Check quorum
+        Ok(())
+    }
+
+    /// Add a delegator's weight to their delegate's vote (open ballots only)
+    /// The weight counts in the delegate's tally entry, so it follows the
+    /// delegate's current budget through any re-vote. The delegator's
+    /// BudgetVote records where it went, so a direct vote from them or a
+    /// revoke takes it back out.
+    pub fn cast_delegated_vote(ctx: Context<CastDelegatedVote>) -> Result<()> {
+        let task = &ctx.accounts.task;
         require!(
-            aggregate.total_voters >= MIN_VOTERS,
-            BudgetVoteError::QuorumNotMet
+            task.budget_ballot == BudgetBallot::Open,
+            BudgetVoteError::CommitRevealBallot
+        );
+        let clock = Clock::get()?;
+        let voting_ends_at = task
+            .budget_voting_ends_at()
+            .ok_or(BudgetVoteError::VotingNotActive)?;
+        require!(
+            clock.unix_timestamp <= voting_ends_at,
+            BudgetVoteError::VotingNotActive
         );
 
-        let total_voted_weight = aggregate.total_weight;
-        let participation_pct = (total_voted_weight * 100) / total_contributed;
+        // A campaign delegation only applies if there is no task delegation
+        let delegation = &ctx.accounts.delegation;
+        require!(
+            delegation.applies_to(&task.key(), &task.campaign),
+            BudgetVoteError::InvalidDelegation
+        );
+        require!(
+            delegation.key() == ctx.accounts.task_delegation.key()
+                || ctx.accounts.task_delegation.data_is_empty(),
+            BudgetVoteError::SupersededByTaskDelegation
+        );
 
+        let contribution = &ctx.accounts.contribution;
+        let contribution_amount = contribution.snapshot_weight();
         require!(
-            participation_pct >= QUORUM_PERCENTAGE as u64,
-            BudgetVoteError::QuorumNotMet
+            contribution_amount >= MIN_CONTRIBUTION_FOR_VOTE,
+            BudgetVoteError::ContributionTooSmall
         );
 
-        // In production: fetch all votes, calculate weighted median
-        // For now, simplified: use average or placeholder
-        // The weighted_median module has the algorithm
+        let vote = &mut ctx.accounts.budget_vote;
+        require!(!vote.is_direct(), BudgetVoteError::DelegatorVotedDirectly);
+        let updated = vote.voter != Pubkey::default();
+
+        // Move the weight off whichever delegate carried it before
+        let aggregate = &mut ctx.accounts.budget_aggregate;
+        let delegate_vote = &mut ctx.accounts.delegate_vote;
+        if vote.delegated_by == Some(delegate_vote.voter) {
+            let remaining = delegate_vote
+                .delegated_weight
+                .checked_sub(vote.vote_weight)
+                .ok_or(BudgetVoteError::VoteNotInTally)?;
+            set_delegated_weight(delegate_vote, aggregate, remaining)?;
+        } else {
+            release_delegated_vote(vote, ctx.accounts.previous_delegate_vote.as_deref_mut(), aggregate)?;
+        }
+
+        let delegated_weight = delegate_vote
+            .delegated_weight
+            .checked_add(contribution_amount)
+            .ok_or(BudgetVoteError::VoteNotInTally)?;
+        set_delegated_weight(delegate_vote, aggregate, delegated_weight)?;
+
+        // Counted at the delegate's budget through their entry, not its own
+        vote.task = task.key();
+        vote.voter = ctx.accounts.delegator.key();
+        vote.proposed_budget = 0;
+        vote.vote_weight = contribution_amount;
+        vote.voted_at = clock.unix_timestamp;
+        vote.delegated_by = Some(delegate_vote.voter);
+
+        emit!(DelegatedBudgetVoteCast {
+            task: task.key(),
+            delegator: ctx.accounts.delegator.key(),
+            delegate: delegate_vote.voter,
+            proposed_budget: delegate_vote.proposed_budget,
+            vote_weight: contribution_amount,
+            delegate_effective_weight: delegate_vote.effective_weight(),
+            updated,
+            cast_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
 
-        let finalized_budget = calculate_weighted_median_placeholder(total_voted_weight);
+    /// Commit a hashed budget vote (commit-reveal ballots only)
+    /// commitment = sha256(proposed_budget as LE bytes || salt); a new commit
+    /// replaces the voter's previous one until voting closes
+    /// The weight stored here is informational: reveal_vote re-reads the
+    /// contribution's snapshot weight, which refunds can't change while
+    /// voting is open, and tallies that.
+    pub fn commit_vote(ctx: Context<SubmitVote>, commitment: [u8; 32]) -> Result<()> {
+        let contribution = ctx
+            .accounts
+            .contribution
+            .as_ref()
+            .ok_or(BudgetVoteError::ContributionTooSmall)?;
+        let contribution_amount = contribution.snapshot_weight();
+        require!(
+            contribution_amount >= MIN_CONTRIBUTION_FOR_VOTE,
+            BudgetVoteError::ContributionTooSmall
+        );
+
+        let task = &ctx.accounts.task;
+        require!(
+            matches!(task.budget_ballot, BudgetBallot::CommitReveal { .. }),
+            BudgetVoteError::OpenBallot
+        );
+        let clock = Clock::get()?;
+        let voting_ends_at = task
+            .budget_voting_ends_at()
+            .ok_or(BudgetVoteError::VotingNotActive)?;
+        require!(
+            clock.unix_timestamp <= voting_ends_at,
+            BudgetVoteError::VotingNotActive
+        );
+
+        let vote = &mut ctx.accounts.budget_vote;
+        let updated = vote.commitment.is_some();
+        if !updated {
+            ctx.accounts.budget_aggregate.unrevealed_commits += 1;
+        }
+
+        vote.task = task.key();
+        vote.voter = ctx.accounts.voter.key();
+        vote.vote_weight = contribution_amount;
+        vote.voted_at = clock.unix_timestamp;
+        vote.commitment = Some(commitment);
+
+        emit!(BudgetVoteCommitted {
+            task: task.key(),
+            voter: ctx.accounts.voter.key(),
+            commitment,
+            updated,
+            committed_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Reveal a committed budget vote during the reveal phase
+    /// Weight is the voter's snapshot weight at reveal time; commits that
+    /// are never revealed stay out of the tally and don't count toward quorum
+    pub fn reveal_vote(ctx: Context<RevealVote>, proposed_budget: u64, salt: [u8; 32]) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let clock = Clock::get()?;
+        let voting_ends_at = task
+            .budget_voting_ends_at()
+            .ok_or(BudgetVoteError::RevealNotActive)?;
+        let reveal_ends_at = task
+            .budget_reveal_ends_at()
+            .ok_or(BudgetVoteError::RevealNotActive)?;
+        require!(
+            matches!(task.budget_ballot, BudgetBallot::CommitReveal { .. }),
+            BudgetVoteError::OpenBallot
+        );
+        require!(
+            clock.unix_timestamp > voting_ends_at && clock.unix_timestamp <= reveal_ends_at,
+            BudgetVoteError::RevealNotActive
+        );
+
+        let vote = &mut ctx.accounts.budget_vote;
+        require!(!vote.revealed, BudgetVoteError::AlreadyRevealed);
+        require!(
+            vote.matches_commitment(proposed_budget, &salt),
+            BudgetVoteError::CommitmentMismatch
+        );
+
+        // A committed budget outside the bounds is rejected or clamped at reveal
+        let bounded_budget = task
+            .bounded_budget(proposed_budget)
+            .ok_or(BudgetVoteError::BudgetOutOfRange)?;
+        let clamped = bounded_budget != proposed_budget;
+        let proposed_budget = bounded_budget;
+
+        let contribution = &ctx.accounts.contribution;
+        let contribution_amount = contribution.snapshot_weight();
+        require!(
+            contribution_amount >= MIN_CONTRIBUTION_FOR_VOTE,
+            BudgetVoteError::ContributionTooSmall
+        );
+
+        let aggregate = &mut ctx.accounts.budget_aggregate;
+        aggregate.record_vote(None, proposed_budget, contribution_amount)?;
+        aggregate.unrevealed_commits = aggregate
+            .unrevealed_commits
+            .checked_sub(1)
+            .ok_or(BudgetVoteError::VoteNotInTally)?;
+
+        vote.proposed_budget = proposed_budget;
+        vote.vote_weight = contribution_amount;
+        vote.revealed = true;
+
+        emit!(BudgetVoteRevealed {
+            task: task.key(),
+            voter: ctx.accounts.voter.key(),
+            proposed_budget,
+            vote_weight: contribution_amount,
+            clamped,
+            revealed_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Finalize budget using the task's aggregation method (weighted median by default)
+    /// Allowed once the voting window has closed with quorum, or earlier when
+    /// participation reaches EARLY_FINALIZE_PERCENTAGE. Commit-reveal ballots
+    /// finalize only after the reveal phase, counting revealed votes alone.
+    pub fn finalize_budget(ctx: Context<FinalizeBudget>) -> Result<()> {
+        let aggregate = &ctx.accounts.budget_aggregate;
+        let clock = Clock::get()?;
+
+        // Quorum is measured against the weight snapshot, not current funding
+        let eligible_weight = ctx.accounts.escrow.total_voting_weight;
+        let total_voted_weight = aggregate.total_weight;
+
+        let can_finalize = match ctx.accounts.task.budget_ballot {
+            BudgetBallot::Open => aggregate.can_finalize(
+                clock.unix_timestamp,
+                eligible_weight,
+                QUORUM_PERCENTAGE,
+                EARLY_FINALIZE_PERCENTAGE,
+                MIN_VOTERS,
+            ),
+            BudgetBallot::CommitReveal { .. } => {
+                let reveal_ends_at = ctx
+                    .accounts
+                    .task
+                    .budget_reveal_ends_at()
+                    .ok_or(BudgetVoteError::RevealPhaseOpen)?;
+                require!(
+                    clock.unix_timestamp > reveal_ends_at,
+                    BudgetVoteError::RevealPhaseOpen
+                );
+                aggregate.meets_quorum(eligible_weight, QUORUM_PERCENTAGE, MIN_VOTERS)
+            }
+        };
+        require!(can_finalize, BudgetVoteError::QuorumNotMet);
+
+        // Every current vote is kept sorted in the aggregate, so this is a
+        // single pass over the list
+        let aggregation = ctx.accounts.task.budget_aggregation.clone();
+        let finalized_budget = aggregate_budget(&aggregation, aggregate.votes.clone());
+        let (min_budget, max_budget) = ctx.accounts.task.budget_range();
 
         emit!(BudgetFinalized {
             task: ctx.accounts.task.key(),
             finalized_budget,
+            aggregation,
+            min_budget,
+            max_budget,
             total_voters: aggregate.total_voters,
             total_vote_weight: total_voted_weight,
-            finalized_at: Clock::get()?.unix_timestamp,
+            unrevealed_commits: aggregate.unrevealed_commits,
+            finalized_at: clock.unix_timestamp,
         });
 
-        // CPI to task_manager to update task
-        // (Would be implemented with actual CPI call)
+        // CPI to task_manager, signed by the aggregate PDA
+        let task_key = ctx.accounts.task.key();
+        let seeds = &[b"budget_aggregate", task_key.as_ref(), &[ctx.bumps.budget_aggregate]];
+        let signer = &[&seeds[..]];
 
-        Ok(())
+        let cpi_accounts = TaskFinalizeBudget {
+            task: ctx.accounts.task.to_account_info(),
+            task_history: ctx.accounts.task_history.to_account_info(),
+            budget_aggregate: ctx.accounts.budget_aggregate.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.task_manager_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        task_manager::cpi::finalize_budget(cpi_ctx, finalized_budget)
     }
 }
 
-// Placeholder for weighted median calculation
-// In production, this fetches all votes and calculates properly
-fn calculate_weighted_median_placeholder(total_weight: u64) -> u64 {
-    // Simplified: return proportional value
-    // Real implementation in weighted_median.rs
-    total_weight / 2
+/// Aggregate size with room for one more vote
+/// Never shrinks, so rent paid by earlier voters isn't handed to the next one
+fn space_for_next_vote(aggregate: &Account<BudgetAggregate>) -> usize {
+    BudgetAggregate::space_for(aggregate.votes.len() + 1).max(aggregate.to_account_info().data_len())
+}
+
+/// Whether an account is owned by `T`'s program and deserializes as a `T`
+fn holds_account<T: AccountDeserialize + Owner>(info: &AccountInfo) -> bool {
+    *info.owner == T::owner() && T::try_deserialize(&mut &info.data.borrow()[..]).is_ok()
+}
+
+/// Take a delegated vote's weight back off the delegate who carries it
+fn release_delegated_vote(
+    vote: &BudgetVote,
+    delegate_vote: Option<&mut BudgetVote>,
+    aggregate: &mut BudgetAggregate,
+) -> Result<()> {
+    let Some(delegate) = vote.delegated_by else {
+        return Ok(());
+    };
+    let delegate_vote = delegate_vote
+        .filter(|dv| dv.voter == delegate && dv.task == vote.task)
+        .ok_or(BudgetVoteError::DelegateVoteRequired)?;
+    let remaining = delegate_vote
+        .delegated_weight
+        .checked_sub(vote.vote_weight)
+        .ok_or(BudgetVoteError::VoteNotInTally)?;
+    set_delegated_weight(delegate_vote, aggregate, remaining)
+}
+
+/// Change the weight delegated to a delegate, keeping their tally entry in step
+fn set_delegated_weight(
+    delegate_vote: &mut BudgetVote,
+    aggregate: &mut BudgetAggregate,
+    delegated_weight: u64,
+) -> Result<()> {
+    let previous = delegate_vote.tally_entry();
+    delegate_vote.delegated_weight = delegated_weight;
+    aggregate.replace_vote(previous.as_ref(), delegate_vote.tally_entry().as_ref())
+}
+
+#[derive(Accounts)]
+pub struct OpenBudgetAggregate<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = BudgetAggregate::space_for(0),
+        seeds = [b"budget_aggregate", task.key().as_ref()],
+        bump
+    )]
+    pub budget_aggregate: Account<'info, BudgetAggregate>,
+
+    #[account(
+        constraint = task.state == TaskState::VotingBudget @ BudgetVoteError::VotingNotActive
+    )]
+    pub task: Account<'info, Task>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -131,17 +549,196 @@ pub struct SubmitVote<'info> {
     )]
     pub budget_vote: Account<'info, BudgetVote>,
 
+    #[account(
+        mut,
+        seeds = [b"budget_aggregate", task.key().as_ref()],
+        bump,
+        realloc = space_for_next_vote(&budget_aggregate),
+        realloc::payer = voter,
+        realloc::zero = false
+    )]
+    pub budget_aggregate: Account<'info, BudgetAggregate>,
+
+    #[account(
+        constraint = task.state == TaskState::VotingBudget @ BudgetVoteError::VotingNotActive
+    )]
+    pub task: Account<'info, Task>,
+
+    /// Voter's contribution receipt from task_escrow (source of vote weight);
+    /// omitted by a delegate voting without a contribution of their own
+    #[account(
+        seeds = [b"contribution", task.key().as_ref(), voter.key().as_ref()],
+        bump,
+        seeds::program = task_escrow::ID,
+        constraint = contribution.task == task.key() @ BudgetVoteError::InvalidContribution,
+        constraint = contribution.contributor == voter.key() @ BudgetVoteError::InvalidContribution
+    )]
+    pub contribution: Option<Account<'info, Contribution>>,
+
+    /// Vote of the delegate who last voted for this voter, if any
+    #[account(mut)]
+    pub previous_delegate_vote: Option<Account<'info, BudgetVote>>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DelegateBudgetVote<'info> {
     #[account(
         init_if_needed,
-        payer = voter,
-        space = BudgetAggregate::LEN,
+        payer = delegator,
+        space = BudgetDelegation::LEN,
+        seeds = [b"budget_delegation", target.key().as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, BudgetDelegation>,
+
+    /// CHECK: Task or campaign being delegated, deserialized by scope in the handler
+    pub target: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    #[account(
+        mut,
+        close = delegator,
+        seeds = [b"budget_delegation", delegation.target.as_ref(), delegator.key().as_ref()],
+        bump = delegation.bump,
+        has_one = delegator
+    )]
+    pub delegation: Account<'info, BudgetDelegation>,
+
+    /// Delegator's vote on a task with an open ballot, to withdraw a vote
+    /// the delegate cast there
+    #[account(mut)]
+    pub budget_vote: Option<Account<'info, BudgetVote>>,
+
+    /// The delegate's vote on the same task, which carries the delegated weight
+    #[account(mut)]
+    pub delegate_vote: Option<Account<'info, BudgetVote>>,
+
+    #[account(mut)]
+    pub budget_aggregate: Option<Account<'info, BudgetAggregate>>,
+
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CastDelegatedVote<'info> {
+    #[account(
+        init_if_needed,
+        payer = delegate,
+        space = BudgetVote::LEN,
+        seeds = [b"budget_vote", task.key().as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub budget_vote: Account<'info, BudgetVote>,
+
+    #[account(
+        mut,
         seeds = [b"budget_aggregate", task.key().as_ref()],
+        bump,
+        realloc = space_for_next_vote(&budget_aggregate),
+        realloc::payer = delegate,
+        realloc::zero = false
+    )]
+    pub budget_aggregate: Account<'info, BudgetAggregate>,
+
+    /// The delegate's own direct vote; delegated weight counts in its entry
+    #[account(
+        mut,
+        seeds = [b"budget_vote", task.key().as_ref(), delegate.key().as_ref()],
+        bump,
+        constraint = delegate_vote.is_direct() @ BudgetVoteError::DelegateHasNotVoted
+    )]
+    pub delegate_vote: Account<'info, BudgetVote>,
+
+    #[account(
+        seeds = [b"budget_delegation", delegation.target.as_ref(), delegator.key().as_ref()],
+        bump = delegation.bump,
+        has_one = delegator @ BudgetVoteError::InvalidDelegation,
+        has_one = delegate @ BudgetVoteError::InvalidDelegation
+    )]
+    pub delegation: Account<'info, BudgetDelegation>,
+
+    /// CHECK: Address of the delegator's task delegation, which must be empty
+    /// when casting under a campaign delegation
+    #[account(
+        seeds = [b"budget_delegation", task.key().as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub task_delegation: UncheckedAccount<'info>,
+
+    /// Vote of a different delegate who last voted for the delegator, if any
+    #[account(mut)]
+    pub previous_delegate_vote: Option<Account<'info, BudgetVote>>,
+
+    #[account(
+        constraint = task.state == TaskState::VotingBudget @ BudgetVoteError::VotingNotActive
+    )]
+    pub task: Account<'info, Task>,
+
+    /// Delegator's contribution receipt from task_escrow (source of vote weight)
+    #[account(
+        seeds = [b"contribution", task.key().as_ref(), delegator.key().as_ref()],
+        bump,
+        seeds::program = task_escrow::ID,
+        constraint = contribution.task == task.key() @ BudgetVoteError::InvalidContribution,
+        constraint = contribution.contributor == delegator.key() @ BudgetVoteError::InvalidContribution
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    /// CHECK: Delegator, matched against the delegation
+    pub delegator: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub delegate: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealVote<'info> {
+    #[account(
+        mut,
+        seeds = [b"budget_vote", task.key().as_ref(), voter.key().as_ref()],
         bump
     )]
+    pub budget_vote: Account<'info, BudgetVote>,
+
+    #[account(
+        mut,
+        seeds = [b"budget_aggregate", task.key().as_ref()],
+        bump,
+        realloc = space_for_next_vote(&budget_aggregate),
+        realloc::payer = voter,
+        realloc::zero = false
+    )]
     pub budget_aggregate: Account<'info, BudgetAggregate>,
 
-    /// CHECK: Task account from task_manager
-    pub task: UncheckedAccount<'info>,
+    #[account(
+        constraint = task.state == TaskState::VotingBudget @ BudgetVoteError::RevealNotActive
+    )]
+    pub task: Account<'info, Task>,
+
+    /// Voter's contribution receipt from task_escrow (source of vote weight)
+    #[account(
+        seeds = [b"contribution", task.key().as_ref(), voter.key().as_ref()],
+        bump,
+        seeds::program = task_escrow::ID,
+        constraint = contribution.task == task.key() @ BudgetVoteError::InvalidContribution,
+        constraint = contribution.contributor == voter.key() @ BudgetVoteError::InvalidContribution
+    )]
+    pub contribution: Account<'info, Contribution>,
 
     #[account(mut)]
     pub voter: Signer<'info>,
@@ -157,10 +754,26 @@ pub struct FinalizeBudget<'info> {
     )]
     pub budget_aggregate: Account<'info, BudgetAggregate>,
 
-    /// CHECK: Task account
-    pub task: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = task.state == TaskState::VotingBudget @ BudgetVoteError::VotingNotActive
+    )]
+    pub task: Account<'info, Task>,
+
+    /// CHECK: Task history PDA, validated by task_manager
+    #[account(mut)]
+    pub task_history: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"escrow", task.key().as_ref()],
+        bump = escrow.bump,
+        seeds::program = task_escrow::ID
+    )]
+    pub escrow: Account<'info, Escrow>,
 
     pub authority: Signer<'info>,
+
+    pub task_manager_program: Program<'info, TaskManager>,
 }
 
 #[event]
@@ -169,15 +782,72 @@ pub struct BudgetVoteSubmitted {
     pub voter: Pubkey,
     pub proposed_budget: u64,
     pub vote_weight: u64,
+    pub updated: bool,  // Replaced the voter's previous vote
+    pub clamped: bool,  // Proposal was outside the task's bounds and counts at the nearest one
     pub voted_at: i64,
 }
 
+#[event]
+pub struct BudgetVoteDelegated {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub scope: DelegationScope,
+    pub target: Pubkey,
+    pub updated: bool,  // Replaced the delegator's previous delegate for this target
+    pub delegated_at: i64,
+}
+
+#[event]
+pub struct BudgetDelegationRevoked {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub scope: DelegationScope,
+    pub target: Pubkey,
+    pub withdrawn_weight: u64,  // Delegated vote taken back out of the passed task's tally
+    pub revoked_at: i64,
+}
+
+#[event]
+pub struct DelegatedBudgetVoteCast {
+    pub task: Pubkey,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub proposed_budget: u64,
+    pub vote_weight: u64,
+    pub delegate_effective_weight: u64,  // Delegate's own weight plus everything delegated to them
+    pub updated: bool,
+    pub cast_at: i64,
+}
+
+#[event]
+pub struct BudgetVoteCommitted {
+    pub task: Pubkey,
+    pub voter: Pubkey,
+    pub commitment: [u8; 32],
+    pub updated: bool,  // Replaced the voter's previous commitment
+    pub committed_at: i64,
+}
+
+#[event]
+pub struct BudgetVoteRevealed {
+    pub task: Pubkey,
+    pub voter: Pubkey,
+    pub proposed_budget: u64,
+    pub vote_weight: u64,
+    pub clamped: bool,
+    pub revealed_at: i64,
+}
+
 #[event]
 pub struct BudgetFinalized {
     pub task: Pubkey,
     pub finalized_budget: u64,
+    pub aggregation: BudgetAggregation,
+    pub min_budget: u64,  // Task's budget bounds for this vote
+    pub max_budget: u64,
     pub total_voters: u32,
     pub total_vote_weight: u64,
+    pub unrevealed_commits: u32,  // Commit-reveal ballots: excluded from the tally
     pub finalized_at: i64,
 }
 
@@ -186,9 +856,62 @@ pub enum BudgetVoteError {
     #[msg("Contribution too small - minimum $10 USDC required to vote")]
     ContributionTooSmall,
 
-    #[msg("Quorum not met - need 60% of funds to vote and at least 3 voters")]
+    #[msg("Quorum not met - need 60% of funds to vote after voting closes (80% to finalize early) and at least 3 voters")]
     QuorumNotMet,
 
     #[msg("Budget voting period not active")]
     VotingNotActive,
+
+    /// Deprecated: votes live in per-voter accounts, so nothing can fill up.
+    /// Kept so the codes of later variants don't shift.
+    #[msg("Vote list is full (no longer returned)")]
+    TooManyVoters,
+
+    #[msg("Contribution does not belong to this task and voter")]
+    InvalidContribution,
+
+    #[msg("This budget vote is commit-reveal - commit a hashed vote instead")]
+    CommitRevealBallot,
+
+    #[msg("This budget vote has an open ballot")]
+    OpenBallot,
+
+    #[msg("Reveal phase not active")]
+    RevealNotActive,
+
+    #[msg("Reveal phase still open")]
+    RevealPhaseOpen,
+
+    #[msg("Vote already revealed")]
+    AlreadyRevealed,
+
+    #[msg("Revealed budget and salt don't match the commitment")]
+    CommitmentMismatch,
+
+    #[msg("Cannot delegate to yourself")]
+    SelfDelegation,
+
+    #[msg("Delegation target must be a task or campaign matching the scope")]
+    InvalidDelegationTarget,
+
+    #[msg("Delegation does not cover this task, delegator and delegate")]
+    InvalidDelegation,
+
+    #[msg("A task delegation overrides this campaign delegation")]
+    SupersededByTaskDelegation,
+
+    #[msg("Delegate must vote directly before casting delegated votes")]
+    DelegateHasNotVoted,
+
+    #[msg("Delegator already voted directly")]
+    DelegatorVotedDirectly,
+
+    #[msg("Pass the vote of the delegate who last voted for this voter")]
+    DelegateVoteRequired,
+
+    #[msg("Proposed budget is outside the task's budget bounds")]
+    BudgetOutOfRange,
+
+    #[msg("Previous vote is missing from the tally")]
+    VoteNotInTally,
 }
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::aggregation::WeightedVote;
 
 #[account]
 pub struct BudgetVote {
@@ -7,10 +9,77 @@ pub struct BudgetVote {
     pub proposed_budget: u64,
     pub vote_weight: u64,  // ANTI-SYBIL: This is contribution amount in USDC, not "1 per wallet"
     pub voted_at: i64,
+    pub commitment: Option<[u8; 32]>,  // Commit-reveal ballots: hash(proposed_budget || salt)
+    pub revealed: bool,  // Commit-reveal ballots: counted in the tally once revealed
+    pub delegated_by: Option<Pubkey>,  // Delegate who cast this vote; None if the voter cast it
+    pub delegated_weight: u64,  // Weight delegated to this voter, counted at their budget
 }
 
 impl BudgetVote {
-    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8;
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + (1 + 32) + 1 + (1 + 32) + 8;
+
+    /// The voter cast this vote themselves; delegates can't overwrite it
+    pub fn is_direct(&self) -> bool {
+        self.voter != Pubkey::default() && self.delegated_by.is_none()
+    }
+
+    /// Weight this voter carries: their own plus what was delegated to them
+    pub fn effective_weight(&self) -> u64 {
+        self.vote_weight.saturating_add(self.delegated_weight)
+    }
+
+    /// This vote's entry in an open ballot's tally: direct votes count at
+    /// their budget with their effective weight, so delegated weight moves
+    /// whenever the delegate re-votes. Delegated votes have no entry of
+    /// their own, and a vote carrying no weight isn't counted.
+    pub fn tally_entry(&self) -> Option<WeightedVote> {
+        let weight = self.effective_weight();
+        (self.is_direct() && weight > 0).then_some(WeightedVote {
+            proposed_budget: self.proposed_budget,
+            weight,
+        })
+    }
+
+    /// Commitment for a commit-reveal ballot: sha256(proposed_budget as LE bytes || salt)
+    pub fn commitment_hash(proposed_budget: u64, salt: &[u8; 32]) -> [u8; 32] {
+        hashv(&[&proposed_budget.to_le_bytes(), salt]).to_bytes()
+    }
+
+    /// Whether a reveal matches this voter's commitment
+    pub fn matches_commitment(&self, proposed_budget: u64, salt: &[u8; 32]) -> bool {
+        self.commitment == Some(Self::commitment_hash(proposed_budget, salt))
+    }
+}
+
+/// How far a budget delegation reaches
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum DelegationScope {
+    Task,      // One task's budget vote
+    Campaign,  // Every task in a campaign, unless a task delegation overrides it
+}
+
+/// A donor's standing delegation of their budget vote weight
+/// PDA seeds: ["budget_delegation", target, delegator]
+#[account]
+pub struct BudgetDelegation {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub scope: DelegationScope,
+    pub target: Pubkey,  // Task or campaign, by scope
+    pub delegated_at: i64,
+    pub bump: u8,
+}
+
+impl BudgetDelegation {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 32 + 8 + 1;
+
+    /// Whether this delegation covers a task in the given campaign
+    pub fn applies_to(&self, task: &Pubkey, campaign: &Pubkey) -> bool {
+        match self.scope {
+            DelegationScope::Task => self.target == *task,
+            DelegationScope::Campaign => self.target == *campaign,
+        }
+    }
 }
 
 #[account]
@@ -18,8 +87,330 @@ pub struct BudgetAggregate {
     pub task: Pubkey,
     pub total_voters: u32,
     pub total_weight: u64,  // Total USDC value of all votes
+    pub voting_ends_at: i64,  // budget_voting_started_at + the task's voting duration
+    pub unrevealed_commits: u32,  // Commit-reveal ballots: committed but not yet revealed
+    pub votes: Vec<WeightedVote>,  // Every current vote, sorted by proposed_budget
 }
 
 impl BudgetAggregate {
-    pub const LEN: usize = 8 + 32 + 4 + 8;
+    /// Size without any votes; each vote adds 16 bytes
+    pub const BASE_LEN: usize = 8 + 32 + 4 + 8 + 8 + 4 + 4;
+
+    /// Account size holding `votes` entries. The account grows by one entry
+    /// as each new voter arrives, so the list has no fixed cap; in practice
+    /// very large tallies need a bigger heap frame to deserialize.
+    pub fn space_for(votes: usize) -> usize {
+        Self::BASE_LEN + votes * 16
+    }
+
+    /// Quorum: enough voters and at least `quorum_pct` of the eligible weight voted
+    pub fn meets_quorum(&self, eligible_weight: u64, quorum_pct: u8, min_voters: u32) -> bool {
+        self.total_voters >= min_voters
+            && eligible_weight > 0
+            && (self.total_weight as u128) * 100 >= (eligible_weight as u128) * quorum_pct as u128
+    }
+
+    /// Finalizable once voting has closed with quorum, or early once
+    /// participation reaches the early-finalize threshold
+    pub fn can_finalize(
+        &self,
+        current_time: i64,
+        eligible_weight: u64,
+        quorum_pct: u8,
+        early_finalize_pct: u8,
+        min_voters: u32,
+    ) -> bool {
+        if current_time > self.voting_ends_at {
+            self.meets_quorum(eligible_weight, quorum_pct, min_voters)
+        } else {
+            self.meets_quorum(eligible_weight, early_finalize_pct, min_voters)
+        }
+    }
+
+    /// Record a voter's vote; a re-vote replaces their previous budget and
+    /// weight instead of adding to them. Returns whether it was a re-vote.
+    /// Errors if the previous vote isn't in the list, rather than leaving a
+    /// stale entry behind.
+    pub fn record_vote(
+        &mut self,
+        previous: Option<&WeightedVote>,
+        proposed_budget: u64,
+        weight: u64,
+    ) -> Result<bool> {
+        self.replace_vote(previous, Some(&WeightedVote { proposed_budget, weight }))?;
+        Ok(previous.is_some())
+    }
+
+    /// Swap a voter's entry for another; None on either side means the
+    /// voter wasn't or no longer is counted
+    pub fn replace_vote(
+        &mut self,
+        previous: Option<&WeightedVote>,
+        current: Option<&WeightedVote>,
+    ) -> Result<()> {
+        if let Some(old) = previous {
+            require!(
+                self.remove_vote(old.proposed_budget, old.weight),
+                crate::BudgetVoteError::VoteNotInTally
+            );
+            self.total_weight = self.total_weight.checked_sub(old.weight).unwrap();
+            self.total_voters -= 1;
+        }
+
+        if let Some(new) = current {
+            self.insert_vote(new.proposed_budget, new.weight);
+            self.total_weight = self.total_weight.checked_add(new.weight).unwrap();
+            self.total_voters += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Insert a vote keeping the list sorted by proposed_budget
+    pub fn insert_vote(&mut self, proposed_budget: u64, weight: u64) {
+        let at = self.votes.partition_point(|v| v.proposed_budget <= proposed_budget);
+        self.votes.insert(at, WeightedVote { proposed_budget, weight });
+    }
+
+    /// Remove a previously inserted vote (any entry with the same budget and weight)
+    pub fn remove_vote(&mut self, proposed_budget: u64, weight: u64) -> bool {
+        let start = self.votes.partition_point(|v| v.proposed_budget < proposed_budget);
+        let found = self.votes[start..]
+            .iter()
+            .take_while(|v| v.proposed_budget == proposed_budget)
+            .position(|v| v.weight == weight);
+
+        match found {
+            Some(offset) => {
+                self.votes.remove(start + offset);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregation::calculate_weighted_median;
+    use proptest::prelude::*;
+    use std::collections::BTreeMap;
+
+    const MIN_WEIGHT: u64 = crate::MIN_CONTRIBUTION_FOR_VOTE;
+
+    fn aggregate() -> BudgetAggregate {
+        BudgetAggregate {
+            task: Pubkey::default(),
+            total_voters: 0,
+            total_weight: 0,
+            voting_ends_at: 0,
+            unrevealed_commits: 0,
+            votes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_vote_list_stays_sorted() {
+        let mut agg = aggregate();
+        for (budget, weight) in [(300, 1), (100, 2), (200, 3), (100, 4)] {
+            agg.insert_vote(budget, weight);
+        }
+
+        let budgets: Vec<u64> = agg.votes.iter().map(|v| v.proposed_budget).collect();
+        assert_eq!(budgets, vec![100, 100, 200, 300]);
+
+        // Re-vote: old entry out, new entry in
+        assert!(agg.remove_vote(100, 4));
+        assert!(!agg.remove_vote(100, 4));
+        agg.insert_vote(250, 4);
+
+        let budgets: Vec<u64> = agg.votes.iter().map(|v| v.proposed_budget).collect();
+        assert_eq!(budgets, vec![100, 200, 250, 300]);
+        assert_eq!(calculate_weighted_median(agg.votes.clone()), 200);
+    }
+
+    #[test]
+    fn test_vote_list_grows() {
+        let mut agg = aggregate();
+        assert_eq!(agg.try_to_vec().unwrap().len() + 8, BudgetAggregate::space_for(0));
+        for i in 0..2_000u64 {
+            agg.record_vote(None, i, MIN_WEIGHT).unwrap();
+            assert_eq!(agg.try_to_vec().unwrap().len() + 8, BudgetAggregate::space_for(agg.votes.len()));
+        }
+        assert_eq!(agg.total_voters, 2_000);
+    }
+
+    #[test]
+    fn test_revote_must_match_tally() {
+        let mut agg = aggregate();
+        agg.record_vote(None, 100, 50).unwrap();
+
+        // A previous vote the list doesn't hold is an error, not a stale entry
+        let stale = WeightedVote { proposed_budget: 100, weight: 60 };
+        assert!(agg.record_vote(Some(&stale), 200, 60).is_err());
+        assert_eq!(agg.votes, vec![WeightedVote { proposed_budget: 100, weight: 50 }]);
+        assert_eq!(agg.total_weight, 50);
+    }
+
+    #[test]
+    fn test_revote_replaces_previous() {
+        let mut agg = aggregate();
+        assert!(!agg.record_vote(None, 100, 50).unwrap());
+        let previous = WeightedVote { proposed_budget: 100, weight: 50 };
+        assert!(agg.record_vote(Some(&previous), 300, 70).unwrap());
+
+        assert_eq!(agg.total_voters, 1);
+        assert_eq!(agg.total_weight, 70);
+        assert_eq!(agg.votes, vec![WeightedVote { proposed_budget: 300, weight: 70 }]);
+    }
+
+    fn budget_vote(proposed_budget: u64, vote_weight: u64) -> BudgetVote {
+        BudgetVote {
+            task: Pubkey::default(),
+            voter: Pubkey::new_unique(),
+            proposed_budget,
+            vote_weight,
+            voted_at: 0,
+            commitment: None,
+            revealed: false,
+            delegated_by: None,
+            delegated_weight: 0,
+        }
+    }
+
+    #[test]
+    fn test_delegated_weight_follows_delegate() {
+        let mut agg = aggregate();
+
+        // A delegate with no contribution of their own isn't counted until
+        // weight is delegated to them
+        let mut delegate = budget_vote(100, 0);
+        assert_eq!(delegate.tally_entry(), None);
+
+        let before = delegate.tally_entry();
+        delegate.delegated_weight += 40;
+        agg.replace_vote(before.as_ref(), delegate.tally_entry().as_ref()).unwrap();
+        assert_eq!(agg.votes, vec![WeightedVote { proposed_budget: 100, weight: 40 }]);
+        assert_eq!(agg.total_voters, 1);
+
+        // The delegate re-votes and the delegated weight moves with them
+        let before = delegate.tally_entry();
+        delegate.proposed_budget = 300;
+        agg.replace_vote(before.as_ref(), delegate.tally_entry().as_ref()).unwrap();
+        assert_eq!(agg.votes, vec![WeightedVote { proposed_budget: 300, weight: 40 }]);
+
+        // Withdrawn delegation leaves nothing behind
+        let before = delegate.tally_entry();
+        delegate.delegated_weight -= 40;
+        agg.replace_vote(before.as_ref(), delegate.tally_entry().as_ref()).unwrap();
+        assert!(agg.votes.is_empty());
+        assert_eq!((agg.total_voters, agg.total_weight), (0, 0));
+
+        // A delegated vote has no entry of its own
+        let mut delegator = budget_vote(0, 40);
+        delegator.delegated_by = Some(delegate.voter);
+        assert_eq!(delegator.tally_entry(), None);
+    }
+
+    #[test]
+    fn test_finalize_window() {
+        let mut agg = aggregate();
+        agg.voting_ends_at = 100;
+        for (budget, weight) in [(100, 30), (200, 20), (300, 20)] {
+            agg.record_vote(None, budget, weight).unwrap();
+        }
+
+        // 70 of 100 voted: enough for quorum, not for early finalize
+        assert!(!agg.can_finalize(100, 100, 60, 80, 3));
+        assert!(agg.can_finalize(101, 100, 60, 80, 3));
+
+        // 70 of 80 voted: early finalize allowed
+        assert!(agg.can_finalize(50, 80, 60, 80, 3));
+
+        // Too few voters either way
+        assert!(!agg.can_finalize(101, 100, 60, 80, 4));
+        assert!(!agg.can_finalize(101, 0, 60, 80, 3));
+    }
+
+    #[test]
+    fn test_commitment() {
+        let salt = [7u8; 32];
+        let mut vote = BudgetVote {
+            task: Pubkey::default(),
+            voter: Pubkey::default(),
+            proposed_budget: 0,
+            vote_weight: 0,
+            voted_at: 0,
+            commitment: Some(BudgetVote::commitment_hash(250, &salt)),
+            revealed: false,
+            delegated_by: None,
+            delegated_weight: 0,
+        };
+
+        assert!(vote.matches_commitment(250, &salt));
+        assert!(!vote.matches_commitment(251, &salt));
+        assert!(!vote.matches_commitment(250, &[8u8; 32]));
+
+        vote.commitment = None;
+        assert!(!vote.matches_commitment(250, &salt));
+    }
+
+    #[test]
+    fn test_delegation_scope() {
+        let (task, campaign) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut delegation = BudgetDelegation {
+            delegator: Pubkey::new_unique(),
+            delegate: Pubkey::new_unique(),
+            scope: DelegationScope::Task,
+            target: task,
+            delegated_at: 0,
+            bump: 0,
+        };
+        assert!(delegation.applies_to(&task, &campaign));
+        assert!(!delegation.applies_to(&Pubkey::new_unique(), &campaign));
+
+        delegation.scope = DelegationScope::Campaign;
+        delegation.target = campaign;
+        assert!(delegation.applies_to(&task, &campaign));
+        assert!(delegation.applies_to(&Pubkey::new_unique(), &campaign));
+        assert!(!delegation.applies_to(&task, &Pubkey::new_unique()));
+    }
+
+    fn sorted_entries(agg: &BudgetAggregate) -> Vec<(u64, u64)> {
+        let mut entries: Vec<(u64, u64)> = agg.votes.iter().map(|v| (v.proposed_budget, v.weight)).collect();
+        entries.sort_unstable();
+        entries
+    }
+
+    proptest! {
+        /// Any sequence of votes and re-votes ends in the same aggregate as
+        /// tallying each voter's final vote once
+        #[test]
+        fn prop_revotes_match_fresh_tally(
+            votes in prop::collection::vec((0u8..8, 0u64..1_000, 10_000_000u64..1_000_000_000_000), 1..60)
+        ) {
+            let mut agg = aggregate();
+            let mut latest: BTreeMap<u8, WeightedVote> = BTreeMap::new();
+            for (voter, budget, weight) in votes {
+                let updated = agg.record_vote(latest.get(&voter), budget, weight).unwrap();
+                prop_assert_eq!(updated, latest.contains_key(&voter));
+                latest.insert(voter, WeightedVote { proposed_budget: budget, weight });
+                prop_assert!(agg.votes.windows(2).all(|w| w[0].proposed_budget <= w[1].proposed_budget));
+            }
+
+            let mut fresh = aggregate();
+            for vote in latest.values() {
+                fresh.record_vote(None, vote.proposed_budget, vote.weight).unwrap();
+            }
+
+            prop_assert_eq!(agg.total_voters, fresh.total_voters);
+            prop_assert_eq!(agg.total_weight, fresh.total_weight);
+            prop_assert_eq!(sorted_entries(&agg), sorted_entries(&fresh));
+            prop_assert_eq!(
+                calculate_weighted_median(agg.votes.clone()),
+                calculate_weighted_median(fresh.votes.clone())
+            );
+        }
+    }
 }
@@ -13,12 +13,27 @@ pub struct IncrementTaskCount<'info> {
     )]
     pub campaign: Account<'info, Campaign>,
 
-    /// The task being added (for event emission)
-    /// CHECK: This is just for event, validated by task_manager program
-    pub task: UncheckedAccount<'info>,
+    /// The task being added: task_manager's ["task", campaign, task_id] PDA,
+    /// which only task_manager can sign for (checked in the handler)
+    pub task: Signer<'info>,
 }
 
-pub fn handler(ctx: Context<IncrementTaskCount>) -> Result<()> {
+/// Task PDA task_manager derives for a task in this campaign
+fn task_address(campaign: &Pubkey, task_id: &str) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"task", campaign.as_ref(), task_id.as_bytes()],
+        &crate::task_manager_program::ID,
+    )
+    .0
+}
+
+pub fn handler(ctx: Context<IncrementTaskCount>, task_id: String) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.task.key(),
+        task_address(&ctx.accounts.campaign.key(), &task_id),
+        CampaignError::UnauthorizedTaskProgram
+    );
+
     let campaign = &mut ctx.accounts.campaign;
     let clock = Clock::get()?;
 
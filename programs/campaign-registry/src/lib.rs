@@ -9,6 +9,12 @@ use instructions::*;
 
 declare_id!("Camp1111111111111111111111111111111111111111");
 
+/// Program allowed to add tasks to campaigns
+pub mod task_manager_program {
+    use super::*;
+    declare_id!("Task1111111111111111111111111111111111111111");
+}
+
 #[program]
 pub mod campaign_registry {
     use super::*;
@@ -47,7 +53,7 @@ pub mod campaign_registry {
     }
 
     /// Increment task count (called by task_manager program via CPI)
-    pub fn increment_task_count(ctx: Context<IncrementTaskCount>) -> Result<()> {
-        instructions::increment_task_count::handler(ctx)
+    pub fn increment_task_count(ctx: Context<IncrementTaskCount>, task_id: String) -> Result<()> {
+        instructions::increment_task_count::handler(ctx, task_id)
     }
 }
@@ -1,26 +1,69 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use task_escrow::cpi::accounts::FreezeEscrow;
+use task_escrow::program::TaskEscrow;
+use task_escrow::state::Contribution;
+use task_manager::cpi::accounts::UpdateTaskState;
+use task_manager::dao_authority;
+use task_manager::program::TaskManager;
+use task_manager::state::{Task, TaskState};
 
 declare_id!("Disp1111111111111111111111111111111111111111");
 
+/// Longest dispute reason stored on-chain
+pub const MAX_REASON_LEN: usize = 500;
+
+/// Time the DAO has to resolve a dispute: 14 days
+pub const RESOLUTION_WINDOW_SECONDS: i64 = 14 * 24 * 60 * 60;
+
 #[program]
 pub mod dispute_module {
     use super::*;
 
-    /// Open a dispute
+    /// Set the DAO multisig that resolves disputes (DAO authority only)
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.bump = ctx.bumps.config;
+
+        Ok(())
+    }
+
+    /// Open a dispute over submitted work (the task's creator, recipients,
+    /// reviewers or donors only)
+    /// The task moves to Disputed (unless a donor veto already put it there)
+    /// and its escrow is frozen until the DAO resolves the dispute. A task's
+    /// dispute account is reused once the previous dispute is settled.
     pub fn open_dispute(
         ctx: Context<OpenDispute>,
         reason: String,
     ) -> Result<()> {
+        require!(reason.len() <= MAX_REASON_LEN, DisputeError::ReasonTooLong);
+        require!(
+            is_task_party(
+                &ctx.accounts.task,
+                &ctx.accounts.initiator.key(),
+                ctx.accounts.contribution.as_deref(),
+            ),
+            DisputeError::NotTaskParty
+        );
+
         let dispute = &mut ctx.accounts.dispute;
         let clock = Clock::get()?;
 
+        require!(
+            dispute.task == Pubkey::default() || dispute.status != DisputeStatus::Open,
+            DisputeError::DisputeAlreadyOpen
+        );
+
         dispute.task = ctx.accounts.task.key();
         dispute.initiator = ctx.accounts.initiator.key();
         dispute.reason = reason.clone();
         dispute.opened_at = clock.unix_timestamp;
-        dispute.resolution_deadline = clock.unix_timestamp + (14 * 24 * 60 * 60); // 14 days
+        dispute.resolution_deadline = clock.unix_timestamp + RESOLUTION_WINDOW_SECONDS;
         dispute.status = DisputeStatus::Open;
         dispute.resolution = None;
+        dispute.resolved_at = None;
 
         emit!(DisputeOpened {
             task: ctx.accounts.task.key(),
@@ -29,10 +72,36 @@ pub mod dispute_module {
             opened_at: clock.unix_timestamp,
         });
 
-        // Would CPI to escrow to freeze funds
-        // freeze_escrow_cpi(ctx.accounts.task.key())?;
+        // Everything below is signed by the dispute PDA
+        let task_key = ctx.accounts.task.key();
+        let seeds = &[
+            b"dispute",
+            task_key.as_ref(),
+            &[ctx.bumps.dispute],
+        ];
+        let signer = &[&seeds[..]];
 
-        Ok(())
+        match ctx.accounts.task.state {
+            TaskState::Disputed => {}
+            TaskState::SubmittedForReview => {
+                let cpi_accounts = UpdateTaskState {
+                    task: ctx.accounts.task.to_account_info(),
+                    task_history: ctx.accounts.task_history.to_account_info(),
+                    authority: ctx.accounts.dispute.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.task_manager_program.to_account_info();
+                task_manager::cpi::dispute_task(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer))?;
+            }
+            _ => return err!(DisputeError::TaskNotDisputable),
+        }
+
+        let cpi_accounts = FreezeEscrow {
+            escrow: ctx.accounts.escrow.to_account_info(),
+            task: ctx.accounts.task.to_account_info(),
+            authority: ctx.accounts.dispute.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.task_escrow_program.to_account_info();
+        task_escrow::cpi::freeze_escrow(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer))
     }
 
     /// Resolve dispute (multisig only)
@@ -58,23 +127,73 @@ pub mod dispute_module {
             resolved_at: clock.unix_timestamp,
         });
 
-        // Would CPI to escrow to unfreeze and execute outcome
+        // Opening the dispute put the task in Disputed: settle it in
+        // task_manager and release the escrow, signed by the dispute PDA
+        // (the task's approval authority while disputed)
+        require!(
+            ctx.accounts.task.state == TaskState::Disputed,
+            DisputeError::TaskNotDisputed
+        );
+
+        let task_key = ctx.accounts.task.key();
+        let seeds = &[
+            b"dispute",
+            task_key.as_ref(),
+            &[ctx.bumps.dispute],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = FreezeEscrow {
+            escrow: ctx.accounts.escrow.to_account_info(),
+            task: ctx.accounts.task.to_account_info(),
+            authority: ctx.accounts.dispute.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.task_escrow_program.to_account_info();
+        task_escrow::cpi::unfreeze_escrow(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer))?;
+
+        let cpi_accounts = UpdateTaskState {
+            task: ctx.accounts.task.to_account_info(),
+            task_history: ctx.accounts.task_history.to_account_info(),
+            authority: ctx.accounts.dispute.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.task_manager_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
         match resolution {
-            DisputeResolution::PayoutToRecipient => {
-                // unfreeze_and_payout_cpi()?;
-            },
+            DisputeResolution::PayoutToRecipient => task_manager::cpi::approve_task(cpi_ctx),
             DisputeResolution::RefundToDonors => {
-                // unfreeze_and_refund_cpi()?;
-            },
-            DisputeResolution::PartialPayoutPartialRefund { payout_percent } => {
-                // unfreeze_and_split_cpi(payout_percent)?;
-            },
+                let reason_hash = hash(ctx.accounts.dispute.reason.as_bytes()).to_bytes();
+                task_manager::cpi::reject_task(cpi_ctx, reason_hash)
+            }
+            // task_manager has no partial outcome for a disputed task
+            DisputeResolution::PartialPayoutPartialRefund { .. } => {
+                err!(DisputeError::PartialResolutionOfDisputedTask)
+            }
         }
-
-        Ok(())
     }
 }
 
+/// Creator, a recipient, a designated reviewer, or a donor with value still held
+fn is_task_party(task: &Task, initiator: &Pubkey, contribution: Option<&Contribution>) -> bool {
+    task.creator == *initiator
+        || task.recipient == Some(*initiator)
+        || task.recipient_splits.iter().any(|split| split.recipient == *initiator)
+        || task.reviewers.contains(initiator)
+        || contribution.is_some_and(|c| c.contributor == *initiator && c.net_amount() > 0)
+}
+
+/// Dispute module configuration
+/// PDA seeds: ["dispute_config"]
+#[account]
+pub struct DisputeConfig {
+    pub authority: Pubkey,   // DAO multisig; only it can resolve disputes
+    pub bump: u8,
+}
+
+impl DisputeConfig {
+    pub const LEN: usize = 8 + 32 + 1;
+}
+
 #[account]
 pub struct Dispute {
     pub task: Pubkey,
@@ -88,7 +207,7 @@ pub struct Dispute {
 }
 
 impl Dispute {
-    pub const LEN: usize = 8 + 32 + 32 + (4 + 500) + 8 + 8 + 1 + (1 + 32) + (1 + 8);
+    pub const LEN: usize = 8 + 32 + 32 + (4 + MAX_REASON_LEN) + 8 + 8 + 1 + (1 + 32) + (1 + 8);
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -106,9 +225,29 @@ pub enum DisputeResolution {
 }
 
 #[derive(Accounts)]
-pub struct OpenDispute<'info> {
+pub struct InitializeConfig<'info> {
     #[account(
         init,
+        payer = authority,
+        space = DisputeConfig::LEN,
+        seeds = [b"dispute_config"],
+        bump
+    )]
+    pub config: Account<'info, DisputeConfig>,
+
+    #[account(
+        mut,
+        address = dao_authority::ID @ DisputeError::UnauthorizedAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(
+        init_if_needed,
         payer = initiator,
         space = Dispute::LEN,
         seeds = [b"dispute", task.key().as_ref()],
@@ -116,17 +255,42 @@ pub struct OpenDispute<'info> {
     )]
     pub dispute: Account<'info, Dispute>,
 
-    /// CHECK: Task account
-    pub task: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub task: Account<'info, Task>,
+
+    /// CHECK: Task history PDA, validated by task_manager
+    #[account(mut)]
+    pub task_history: UncheckedAccount<'info>,
+
+    /// CHECK: Task escrow PDA, validated by task_escrow
+    #[account(mut)]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// Initiator's contribution receipt, when disputing as a donor
+    #[account(
+        seeds = [b"contribution", task.key().as_ref(), initiator.key().as_ref()],
+        bump,
+        seeds::program = task_escrow::ID
+    )]
+    pub contribution: Option<Account<'info, Contribution>>,
 
     #[account(mut)]
     pub initiator: Signer<'info>,
 
+    pub task_manager_program: Program<'info, TaskManager>,
+    pub task_escrow_program: Program<'info, TaskEscrow>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct ResolveDispute<'info> {
+    #[account(
+        seeds = [b"dispute_config"],
+        bump = config.bump,
+        has_one = authority @ DisputeError::UnauthorizedResolver
+    )]
+    pub config: Account<'info, DisputeConfig>,
+
     #[account(
         mut,
         seeds = [b"dispute", task.key().as_ref()],
@@ -134,11 +298,22 @@ pub struct ResolveDispute<'info> {
     )]
     pub dispute: Account<'info, Dispute>,
 
-    /// CHECK: Task account
-    pub task: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub task: Account<'info, Task>,
+
+    /// CHECK: Task history PDA, validated by task_manager
+    #[account(mut)]
+    pub task_history: UncheckedAccount<'info>,
+
+    /// CHECK: Task escrow PDA, validated by task_escrow
+    #[account(mut)]
+    pub escrow: UncheckedAccount<'info>,
 
     /// Multisig authority (DAO)
     pub authority: Signer<'info>,
+
+    pub task_manager_program: Program<'info, TaskManager>,
+    pub task_escrow_program: Program<'info, TaskEscrow>,
 }
 
 #[event]
@@ -163,4 +338,63 @@ pub enum DisputeError {
 
     #[msg("Unauthorized to resolve dispute")]
     UnauthorizedResolver,
+
+    #[msg("A disputed task must be resolved as a full payout or a full refund")]
+    PartialResolutionOfDisputedTask,
+
+    #[msg("Only the DAO authority can initialize the config")]
+    UnauthorizedAuthority,
+
+    #[msg("Dispute reason is too long")]
+    ReasonTooLong,
+
+    #[msg("Only the task's creator, recipients, reviewers or donors can open a dispute")]
+    NotTaskParty,
+
+    #[msg("The task already has an open dispute")]
+    DisputeAlreadyOpen,
+
+    #[msg("Only submitted work can be disputed")]
+    TaskNotDisputable,
+
+    #[msg("Task is not disputed")]
+    TaskNotDisputed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use task_manager::state::DisputeHeader;
+
+    #[test]
+    fn test_task_manager_reads_dispute_layout() {
+        let mut dispute = Dispute {
+            task: Pubkey::new_unique(),
+            initiator: Pubkey::new_unique(),
+            reason: "work not delivered".to_string(),
+            opened_at: 100,
+            resolution_deadline: 100 + RESOLUTION_WINDOW_SECONDS,
+            status: DisputeStatus::Open,
+            resolution: None,
+            resolved_at: None,
+        };
+
+        for (status, open) in [
+            (DisputeStatus::Open, true),
+            (DisputeStatus::Resolved, false),
+            (DisputeStatus::Expired, false),
+        ] {
+            dispute.status = status;
+            let mut data = Vec::new();
+            dispute.try_serialize(&mut data).unwrap();
+
+            let header = DisputeHeader::decode(&data).unwrap();
+            assert_eq!(header.task, dispute.task);
+            assert_eq!(header.initiator, dispute.initiator);
+            assert_eq!(header.reason, dispute.reason);
+            assert_eq!(header.opened_at, dispute.opened_at);
+            assert_eq!(header.resolution_deadline, dispute.resolution_deadline);
+            assert_eq!(header.is_open(dispute.opened_at), open);
+        }
+    }
 }
@@ -1,4 +1,9 @@
 use anchor_lang::prelude::*;
+use task_manager::cpi::accounts::{
+    SubmitBountyEntry as TaskSubmitBountyEntry, SubmitProof as TaskSubmitProof,
+};
+use task_manager::program::TaskManager;
+use task_manager::state::{Task, TaskState, MAX_PROOF_HASH_LEN, MAX_PROOF_URI_LEN};
 
 declare_id!("Prof1111111111111111111111111111111111111111");
 
@@ -6,54 +11,144 @@ declare_id!("Prof1111111111111111111111111111111111111111");
 pub mod proof_registry {
     use super::*;
 
+    /// Submit (or, after requested changes, resubmit) proof for a task
+    /// The Proof account is the single record of the deliverable; the task
+    /// moves to SubmittedForReview via CPI, signed by the proof PDA.
     pub fn submit_proof(
         ctx: Context<SubmitProof>,
         proof_hash: String,
         proof_uri: String,
     ) -> Result<()> {
+        validate_proof(&proof_hash, &proof_uri)?;
+
         let proof = &mut ctx.accounts.proof;
         let clock = Clock::get()?;
+        let task_key = ctx.accounts.task.key();
+        let resubmission = proof.submitted_at != 0;
 
-        proof.task = ctx.accounts.task.key();
+        proof.task = task_key;
         proof.recipient = ctx.accounts.recipient.key();
         proof.proof_hash = proof_hash.clone();
         proof.proof_uri = proof_uri.clone();
-        proof.submitted_at = clock.unix_timestamp;
-
-        emit!(ProofSubmitted {
-            task: ctx.accounts.task.key(),
-            recipient: ctx.accounts.recipient.key(),
-            proof_hash,
-            proof_uri,
-            submitted_at: clock.unix_timestamp,
-        });
-
-        Ok(())
+        if resubmission {
+            proof.updated_at = Some(clock.unix_timestamp);
+        } else {
+            proof.submitted_at = clock.unix_timestamp;
+            proof.bump = ctx.bumps.proof;
+        }
+
+        if resubmission {
+            emit!(ProofUpdated {
+                task: task_key,
+                proof_hash: proof_hash.clone(),
+                proof_uri: proof_uri.clone(),
+                updated_at: clock.unix_timestamp,
+            });
+        } else {
+            emit!(ProofSubmitted {
+                task: task_key,
+                recipient: ctx.accounts.recipient.key(),
+                proof_hash: proof_hash.clone(),
+                proof_uri: proof_uri.clone(),
+                submitted_at: clock.unix_timestamp,
+            });
+        }
+
+        // CPI to task_manager, signed by the proof PDA
+        let seeds = &[b"proof", task_key.as_ref(), &[proof.bump]];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = TaskSubmitProof {
+            task: ctx.accounts.task.to_account_info(),
+            task_history: ctx.accounts.task_history.to_account_info(),
+            proof: ctx.accounts.proof.to_account_info(),
+            recipient: ctx.accounts.recipient.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.task_manager_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        task_manager::cpi::submit_proof(cpi_ctx, proof_hash, proof_uri)
     }
 
-    pub fn update_proof(
-        ctx: Context<UpdateProof>,
+    /// Submit (or replace) a bounty entry before the deadline
+    /// Each participant's entry gets its own Proof account; task_manager's
+    /// BountyEntry only references it, via CPI signed by the entry proof PDA.
+    pub fn submit_bounty_entry(
+        ctx: Context<SubmitBountyEntry>,
         proof_hash: String,
         proof_uri: String,
     ) -> Result<()> {
+        validate_proof(&proof_hash, &proof_uri)?;
+
         let proof = &mut ctx.accounts.proof;
         let clock = Clock::get()?;
+        let task_key = ctx.accounts.task.key();
+        let participant_key = ctx.accounts.participant.key();
+        let resubmission = proof.submitted_at != 0;
 
+        proof.task = task_key;
+        proof.recipient = participant_key;
         proof.proof_hash = proof_hash.clone();
         proof.proof_uri = proof_uri.clone();
-        proof.updated_at = Some(clock.unix_timestamp);
-
-        emit!(ProofUpdated {
-            task: ctx.accounts.task.key(),
-            proof_hash,
-            proof_uri,
-            updated_at: clock.unix_timestamp,
-        });
-
-        Ok(())
+        if resubmission {
+            proof.updated_at = Some(clock.unix_timestamp);
+        } else {
+            proof.submitted_at = clock.unix_timestamp;
+            proof.bump = ctx.bumps.proof;
+        }
+
+        if resubmission {
+            emit!(ProofUpdated {
+                task: task_key,
+                proof_hash: proof_hash.clone(),
+                proof_uri: proof_uri.clone(),
+                updated_at: clock.unix_timestamp,
+            });
+        } else {
+            emit!(ProofSubmitted {
+                task: task_key,
+                recipient: participant_key,
+                proof_hash: proof_hash.clone(),
+                proof_uri: proof_uri.clone(),
+                submitted_at: clock.unix_timestamp,
+            });
+        }
+
+        // CPI to task_manager, signed by the entry proof PDA
+        let seeds = &[
+            b"entry_proof",
+            task_key.as_ref(),
+            participant_key.as_ref(),
+            &[proof.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = TaskSubmitBountyEntry {
+            entry: ctx.accounts.entry.to_account_info(),
+            task: ctx.accounts.task.to_account_info(),
+            proof: ctx.accounts.proof.to_account_info(),
+            participant: ctx.accounts.participant.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.task_manager_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        task_manager::cpi::submit_bounty_entry(cpi_ctx, proof_hash, proof_uri)
     }
 }
 
+fn validate_proof(proof_hash: &str, proof_uri: &str) -> Result<()> {
+    require!(
+        !proof_hash.is_empty() && proof_hash.len() <= MAX_PROOF_HASH_LEN,
+        ProofError::InvalidProofHash
+    );
+    require!(
+        !proof_uri.is_empty() && proof_uri.len() <= MAX_PROOF_URI_LEN,
+        ProofError::InvalidProofUri
+    );
+
+    Ok(())
+}
+
 #[account]
 pub struct Proof {
     pub task: Pubkey,
@@ -62,16 +157,17 @@ pub struct Proof {
     pub proof_uri: String,        // IPFS/Arweave URI
     pub submitted_at: i64,
     pub updated_at: Option<i64>,
+    pub bump: u8,
 }
 
 impl Proof {
-    pub const LEN: usize = 8 + 32 + 32 + (4 + 64) + (4 + 200) + 8 + (1 + 8);
+    pub const LEN: usize = 8 + 32 + 32 + (4 + MAX_PROOF_HASH_LEN) + (4 + MAX_PROOF_URI_LEN) + 8 + (1 + 8) + 1;
 }
 
 #[derive(Accounts)]
 pub struct SubmitProof<'info> {
     #[account(
-        init,
+        init_if_needed,
         payer = recipient,
         space = Proof::LEN,
         seeds = [b"proof", task.key().as_ref()],
@@ -79,29 +175,48 @@ pub struct SubmitProof<'info> {
     )]
     pub proof: Account<'info, Proof>,
 
-    /// CHECK: Task account
-    pub task: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = task.state == TaskState::InProgress @ ProofError::TaskNotInProgress,
+        constraint = task.recipient == Some(recipient.key()) @ ProofError::UnauthorizedRecipient
+    )]
+    pub task: Account<'info, Task>,
+
+    /// CHECK: Task history PDA, validated by task_manager
+    #[account(mut)]
+    pub task_history: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub recipient: Signer<'info>,
 
+    pub task_manager_program: Program<'info, TaskManager>,
+
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateProof<'info> {
+pub struct SubmitBountyEntry<'info> {
     #[account(
-        mut,
-        seeds = [b"proof", task.key().as_ref()],
-        bump,
-        constraint = proof.recipient == recipient.key()
+        init_if_needed,
+        payer = participant,
+        space = Proof::LEN,
+        seeds = [b"entry_proof", task.key().as_ref(), participant.key().as_ref()],
+        bump
     )]
     pub proof: Account<'info, Proof>,
 
-    /// CHECK: Task account
-    pub task: UncheckedAccount<'info>,
+    pub task: Account<'info, Task>,
 
-    pub recipient: Signer<'info>,
+    /// CHECK: Participant's BountyEntry PDA, validated by task_manager
+    #[account(mut)]
+    pub entry: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    pub task_manager_program: Program<'info, TaskManager>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[event]
@@ -120,3 +235,18 @@ pub struct ProofUpdated {
     pub proof_uri: String,
     pub updated_at: i64,
 }
+
+#[error_code]
+pub enum ProofError {
+    #[msg("Task is not in progress")]
+    TaskNotInProgress,
+
+    #[msg("Only the task recipient can submit proof")]
+    UnauthorizedRecipient,
+
+    #[msg("Invalid proof hash")]
+    InvalidProofHash,
+
+    #[msg("Invalid proof URI")]
+    InvalidProofUri,
+}
@@ -0,0 +1,473 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use dispute_module::{Dispute, DisputeStatus};
+use task_manager::cpi::accounts::UpdateTaskState;
+use task_manager::dao_authority;
+use task_manager::program::TaskManager;
+use task_manager::state::{Task, TaskState};
+
+pub mod state;
+use state::*;
+
+declare_id!("Revw1111111111111111111111111111111111111111");
+
+/// Share of stake slashed when a dispute overturns a decision: 50%
+pub const SLASH_BPS: u16 = 5_000;
+
+/// Stake stays locked this long after a decision, covering the 14-day
+/// dispute resolution window with margin: 30 days
+pub const UNSTAKE_COOLDOWN_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+#[program]
+pub mod reviewer_registry {
+    use super::*;
+
+    /// Initialize the registry (DAO authority only)
+    pub fn initialize_registry(ctx: Context<InitializeRegistry>, min_stake: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.obook_mint = ctx.accounts.obook_mint.key();
+        config.min_stake = min_stake;
+        config.bump = ctx.bumps.config;
+
+        Ok(())
+    }
+
+    /// Stake OBOOK as a reviewer (registers on first stake)
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        let clock = Clock::get()?;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.reviewer_token.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.reviewer.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(CpiContext::new(cpi_program, cpi_accounts), amount)?;
+
+        let reviewer_account = &mut ctx.accounts.reviewer_account;
+        if reviewer_account.reviewer == Pubkey::default() {
+            reviewer_account.reviewer = ctx.accounts.reviewer.key();
+            reviewer_account.registered_at = clock.unix_timestamp;
+            reviewer_account.bump = ctx.bumps.reviewer_account;
+        }
+        reviewer_account.stake = reviewer_account.stake.checked_add(amount).unwrap();
+
+        require!(
+            reviewer_account.stake >= ctx.accounts.config.min_stake,
+            ReviewerError::InsufficientStake
+        );
+
+        emit!(ReviewerStaked {
+            reviewer: reviewer_account.reviewer,
+            amount,
+            total_stake: reviewer_account.stake,
+            staked_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw stake once no recent decision can still be disputed
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        let reviewer_account = &ctx.accounts.reviewer_account;
+
+        require!(
+            reviewer_account.can_unstake(clock.unix_timestamp, UNSTAKE_COOLDOWN_SECONDS),
+            ReviewerError::StakeLocked
+        );
+        require!(amount <= reviewer_account.stake, ReviewerError::InsufficientStake);
+
+        let reviewer_key = ctx.accounts.reviewer.key();
+        let seeds = &[
+            b"reviewer",
+            reviewer_key.as_ref(),
+            &[reviewer_account.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.reviewer_token.to_account_info(),
+            authority: ctx.accounts.reviewer_account.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer), amount)?;
+
+        let reviewer_account = &mut ctx.accounts.reviewer_account;
+        reviewer_account.stake -= amount;
+
+        emit!(ReviewerUnstaked {
+            reviewer: reviewer_key,
+            amount,
+            total_stake: reviewer_account.stake,
+            unstaked_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Approve or reject submitted work as a designated, staked reviewer
+    pub fn review_task(
+        ctx: Context<ReviewTask>,
+        approve: bool,
+        rationale_hash: [u8; 32],
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        let decision = &mut ctx.accounts.decision;
+        decision.task = ctx.accounts.task.key();
+        decision.reviewer = ctx.accounts.reviewer.key();
+        decision.round = ctx.accounts.task.revision_count;
+        decision.approved = approve;
+        decision.rationale_hash = rationale_hash;
+        decision.decided_at = clock.unix_timestamp;
+        decision.slashed = false;
+        decision.bump = ctx.bumps.decision;
+
+        let reviewer_account = &mut ctx.accounts.reviewer_account;
+        reviewer_account.decisions_count += 1;
+        reviewer_account.last_decision_at = Some(clock.unix_timestamp);
+
+        emit!(ReviewDecisionMade {
+            task: ctx.accounts.task.key(),
+            reviewer: ctx.accounts.reviewer.key(),
+            approved: approve,
+            rationale_hash,
+            stake: reviewer_account.stake,
+            decided_at: clock.unix_timestamp,
+        });
+
+        // CPI to task_manager, signed by the decision PDA
+        let task_key = ctx.accounts.task.key();
+        let seeds = &[
+            b"review_decision",
+            task_key.as_ref(),
+            &[ctx.accounts.decision.round],
+            &[ctx.accounts.decision.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = UpdateTaskState {
+            task: ctx.accounts.task.to_account_info(),
+            task_history: ctx.accounts.task_history.to_account_info(),
+            authority: ctx.accounts.decision.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.task_manager_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        if approve {
+            task_manager::cpi::approve_task(cpi_ctx)
+        } else {
+            task_manager::cpi::reject_task(cpi_ctx, rationale_hash)
+        }
+    }
+
+    /// Slash a reviewer whose decision was overturned by a resolved dispute
+    /// Permissionless: the dispute outcome on-chain is the evidence
+    pub fn slash_reviewer(ctx: Context<SlashReviewer>) -> Result<()> {
+        let clock = Clock::get()?;
+        let dispute = &ctx.accounts.dispute;
+
+        require!(
+            dispute.status == DisputeStatus::Resolved,
+            ReviewerError::DisputeNotResolved
+        );
+        let resolution = dispute.resolution.clone().ok_or(ReviewerError::DisputeNotResolved)?;
+        require!(
+            ctx.accounts
+                .decision
+                .is_overturned_by(&resolution, ctx.accounts.task.revision_count),
+            ReviewerError::DecisionNotOverturned
+        );
+
+        let reviewer_account = &ctx.accounts.reviewer_account;
+        let amount = reviewer_account.slash_amount(SLASH_BPS);
+
+        let reviewer_key = reviewer_account.reviewer;
+        let seeds = &[
+            b"reviewer",
+            reviewer_key.as_ref(),
+            &[reviewer_account.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.treasury_token.to_account_info(),
+            authority: ctx.accounts.reviewer_account.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer), amount)?;
+
+        let reviewer_account = &mut ctx.accounts.reviewer_account;
+        reviewer_account.stake -= amount;
+        reviewer_account.slashed_total = reviewer_account.slashed_total.checked_add(amount).unwrap();
+
+        let decision = &mut ctx.accounts.decision;
+        decision.slashed = true;
+
+        emit!(ReviewerSlashed {
+            reviewer: reviewer_key,
+            task: decision.task,
+            amount,
+            remaining_stake: reviewer_account.stake,
+            slashed_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = RegistryConfig::LEN,
+        seeds = [b"reviewer_config"],
+        bump
+    )]
+    pub config: Account<'info, RegistryConfig>,
+
+    pub obook_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = dao_authority::ID @ ReviewerError::UnauthorizedAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(
+        seeds = [b"reviewer_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, RegistryConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = reviewer,
+        space = Reviewer::LEN,
+        seeds = [b"reviewer", reviewer.key().as_ref()],
+        bump
+    )]
+    pub reviewer_account: Account<'info, Reviewer>,
+
+    /// OBOOK stake vault owned by the reviewer PDA
+    #[account(
+        init_if_needed,
+        payer = reviewer,
+        seeds = [b"reviewer_vault", reviewer.key().as_ref()],
+        bump,
+        token::mint = obook_mint,
+        token::authority = reviewer_account
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(address = config.obook_mint)]
+    pub obook_mint: Account<'info, Mint>,
+
+    /// Reviewer's OBOOK token account
+    #[account(mut)]
+    pub reviewer_token: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(
+        mut,
+        seeds = [b"reviewer", reviewer.key().as_ref()],
+        bump = reviewer_account.bump
+    )]
+    pub reviewer_account: Account<'info, Reviewer>,
+
+    #[account(
+        mut,
+        seeds = [b"reviewer_vault", reviewer.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub reviewer_token: Account<'info, TokenAccount>,
+
+    pub reviewer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ReviewTask<'info> {
+    #[account(
+        seeds = [b"reviewer_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, RegistryConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"reviewer", reviewer.key().as_ref()],
+        bump = reviewer_account.bump,
+        constraint = reviewer_account.stake >= config.min_stake @ ReviewerError::InsufficientStake
+    )]
+    pub reviewer_account: Account<'info, Reviewer>,
+
+    #[account(
+        init,
+        payer = reviewer,
+        space = ReviewDecision::LEN,
+        seeds = [b"review_decision", task.key().as_ref(), &[task.revision_count]],
+        bump
+    )]
+    pub decision: Account<'info, ReviewDecision>,
+
+    #[account(
+        mut,
+        constraint = task.state == TaskState::SubmittedForReview @ ReviewerError::ReviewNotActive,
+        constraint = task.is_reviewer(&reviewer.key()) @ ReviewerError::NotDesignatedReviewer
+    )]
+    pub task: Account<'info, Task>,
+
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+
+    /// CHECK: Task history PDA, validated by task_manager
+    #[account(mut)]
+    pub task_history: UncheckedAccount<'info>,
+
+    pub task_manager_program: Program<'info, TaskManager>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SlashReviewer<'info> {
+    #[account(
+        seeds = [b"reviewer_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, RegistryConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"review_decision", decision.task.as_ref(), &[decision.round]],
+        bump = decision.bump,
+        constraint = !decision.slashed @ ReviewerError::AlreadySlashed
+    )]
+    pub decision: Account<'info, ReviewDecision>,
+
+    #[account(
+        seeds = [b"dispute", decision.task.as_ref()],
+        bump,
+        seeds::program = dispute_module::ID,
+        constraint = dispute.opened_at >= decision.decided_at @ ReviewerError::DecisionNotOverturned
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// The disputed task; its revision_count is the round the dispute judged
+    #[account(address = decision.task)]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        mut,
+        seeds = [b"reviewer", decision.reviewer.as_ref()],
+        bump = reviewer_account.bump
+    )]
+    pub reviewer_account: Account<'info, Reviewer>,
+
+    #[account(
+        mut,
+        seeds = [b"reviewer_vault", decision.reviewer.as_ref()],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// DAO treasury OBOOK account receiving slashed stake
+    #[account(
+        mut,
+        constraint = treasury_token.owner == config.authority @ ReviewerError::InvalidTreasury,
+        constraint = treasury_token.mint == config.obook_mint @ ReviewerError::InvalidTreasury
+    )]
+    pub treasury_token: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[event]
+pub struct ReviewerStaked {
+    pub reviewer: Pubkey,
+    pub amount: u64,
+    pub total_stake: u64,
+    pub staked_at: i64,
+}
+
+#[event]
+pub struct ReviewerUnstaked {
+    pub reviewer: Pubkey,
+    pub amount: u64,
+    pub total_stake: u64,
+    pub unstaked_at: i64,
+}
+
+#[event]
+pub struct ReviewDecisionMade {
+    pub task: Pubkey,
+    pub reviewer: Pubkey,
+    pub approved: bool,
+    pub rationale_hash: [u8; 32],
+    pub stake: u64,
+    pub decided_at: i64,
+}
+
+#[event]
+pub struct ReviewerSlashed {
+    pub reviewer: Pubkey,
+    pub task: Pubkey,
+    pub amount: u64,
+    pub remaining_stake: u64,
+    pub slashed_at: i64,
+}
+
+#[error_code]
+pub enum ReviewerError {
+    #[msg("Reviewer stake is below the registry minimum")]
+    InsufficientStake,
+
+    #[msg("Stake is locked while recent decisions can still be disputed")]
+    StakeLocked,
+
+    #[msg("Task is not awaiting review")]
+    ReviewNotActive,
+
+    #[msg("Signer is not a designated reviewer for this task")]
+    NotDesignatedReviewer,
+
+    #[msg("Dispute has not been resolved")]
+    DisputeNotResolved,
+
+    #[msg("Dispute outcome does not overturn the review decision")]
+    DecisionNotOverturned,
+
+    #[msg("Decision has already been slashed")]
+    AlreadySlashed,
+
+    #[msg("Treasury account must be the DAO authority's OBOOK account")]
+    InvalidTreasury,
+
+    #[msg("Only the DAO authority can initialize the registry")]
+    UnauthorizedAuthority,
+}
@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use dispute_module::DisputeResolution;
+
+/// Registry configuration
+/// PDA seeds: ["reviewer_config"]
+#[account]
+pub struct RegistryConfig {
+    pub authority: Pubkey,   // DAO multisig; receives slashed stake
+    pub obook_mint: Pubkey,
+    pub min_stake: u64,
+    pub bump: u8,
+}
+
+impl RegistryConfig {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+/// Staked reviewer
+/// PDA seeds: ["reviewer", reviewer]
+#[account]
+pub struct Reviewer {
+    pub reviewer: Pubkey,
+    pub stake: u64,          // OBOOK held in the reviewer's stake vault
+    pub slashed_total: u64,
+    pub decisions_count: u32,
+    pub last_decision_at: Option<i64>,
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+impl Reviewer {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 4 + (1 + 8) + 8 + 1;
+
+    /// Stake is locked until no recent decision can still be disputed
+    pub fn can_unstake(&self, current_time: i64, cooldown: i64) -> bool {
+        match self.last_decision_at {
+            Some(decided_at) => current_time >= decided_at + cooldown,
+            None => true,
+        }
+    }
+
+    /// Portion of stake forfeited when a decision is overturned
+    pub fn slash_amount(&self, slash_bps: u16) -> u64 {
+        ((self.stake as u128 * slash_bps as u128) / 10_000) as u64
+    }
+}
+
+/// A reviewer's decision on one submission of a task
+/// PDA seeds: ["review_decision", task, [round]]
+/// One account per review round so a revision never overwrites an earlier decision
+#[account]
+pub struct ReviewDecision {
+    pub task: Pubkey,
+    pub reviewer: Pubkey,
+    /// Task revision_count when the decision was made
+    pub round: u8,
+    pub approved: bool,
+    pub rationale_hash: [u8; 32],
+    pub decided_at: i64,
+    pub slashed: bool,
+    pub bump: u8,
+}
+
+impl ReviewDecision {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 1 + 32 + 8 + 1 + 1;
+
+    /// A dispute overturns the decision when its outcome contradicts it.
+    /// Only the final round's decision is judged: an earlier "changes
+    /// requested" rejection the recipient went on to address is normal review.
+    /// Partial outcomes are a compromise and do not count as overturning.
+    pub fn is_overturned_by(&self, resolution: &DisputeResolution, final_round: u8) -> bool {
+        if self.round != final_round {
+            return false;
+        }
+        match resolution {
+            DisputeResolution::PayoutToRecipient => !self.approved,
+            DisputeResolution::RefundToDonors => self.approved,
+            DisputeResolution::PartialPayoutPartialRefund { .. } => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decision(approved: bool) -> ReviewDecision {
+        ReviewDecision {
+            task: Pubkey::default(),
+            reviewer: Pubkey::default(),
+            round: 1,
+            approved,
+            rationale_hash: [0; 32],
+            decided_at: 0,
+            slashed: false,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_overturned_decisions() {
+        assert!(decision(true).is_overturned_by(&DisputeResolution::RefundToDonors, 1));
+        assert!(!decision(true).is_overturned_by(&DisputeResolution::PayoutToRecipient, 1));
+        assert!(decision(false).is_overturned_by(&DisputeResolution::PayoutToRecipient, 1));
+        assert!(!decision(false).is_overturned_by(&DisputeResolution::RefundToDonors, 1));
+        assert!(!decision(true).is_overturned_by(
+            &DisputeResolution::PartialPayoutPartialRefund { payout_percent: 50 },
+            1
+        ));
+    }
+
+    #[test]
+    fn test_earlier_round_rejection_not_overturned() {
+        // Round 1 asked for changes, the recipient revised, and the dispute
+        // paid out on round 2: the round 1 rejection was ordinary review
+        let changes_requested = decision(false);
+        assert!(!changes_requested.is_overturned_by(&DisputeResolution::PayoutToRecipient, 2));
+
+        let mut final_rejection = decision(false);
+        final_rejection.round = 2;
+        assert!(final_rejection.is_overturned_by(&DisputeResolution::PayoutToRecipient, 2));
+    }
+
+    #[test]
+    fn test_unstake_cooldown_and_slash() {
+        let mut reviewer = Reviewer {
+            reviewer: Pubkey::default(),
+            stake: 1_000,
+            slashed_total: 0,
+            decisions_count: 0,
+            last_decision_at: None,
+            registered_at: 0,
+            bump: 0,
+        };
+
+        assert!(reviewer.can_unstake(0, 100));
+        reviewer.last_decision_at = Some(50);
+        assert!(!reviewer.can_unstake(149, 100));
+        assert!(reviewer.can_unstake(150, 100));
+
+        assert_eq!(reviewer.slash_amount(5_000), 500);
+        reviewer.stake = u64::MAX;
+        assert_eq!(reviewer.slash_amount(10_000), u64::MAX);
+    }
+}
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use task_manager::cpi::accounts::MarkPaidOut;
+use task_manager::program::TaskManager;
+use task_manager::state::{Task, TaskState};
 
 pub mod state;
 use state::*;
@@ -9,6 +12,18 @@ declare_id!("Escr1111111111111111111111111111111111111111");
 /// Minimum contribution: $10 USDC (6 decimals)
 pub const MIN_CONTRIBUTION: u64 = 10_000_000;
 
+/// Donor vote on moving a failed task's funds: 7 days
+pub const REALLOCATION_VOTING_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// Reallocation passes when more than 50% of held value consents
+pub const REALLOCATION_THRESHOLD_PERCENTAGE: u8 = 50;
+
+/// KYC provider key allowed to record recipient attestations
+pub mod kyc_issuer {
+    use super::*;
+    declare_id!("Kyc1ssuer1111111111111111111111111111111111");
+}
+
 #[program]
 pub mod task_escrow {
     use super::*;
@@ -20,6 +35,8 @@ pub mod task_escrow {
         escrow.total_contributed = 0;
         escrow.total_refunded = 0;
         escrow.total_paid_out = 0;
+        escrow.total_reallocated = 0;
+        escrow.total_voting_weight = 0;
         escrow.is_frozen = false;
         escrow.bump = ctx.bumps.escrow;
 
@@ -51,14 +68,22 @@ pub mod task_escrow {
         let contribution = &mut ctx.accounts.contribution;
         contribution.task = ctx.accounts.task.key();
         contribution.contributor = ctx.accounts.contributor.key();
-        contribution.amount += amount;  // Cumulative
+        contribution.credit(amount).unwrap();  // Cumulative
         contribution.contributed_at = Clock::get()?.unix_timestamp;
-        contribution.refunded = false;
-        contribution.refund_amount = 0;
+
+        // Budget vote weight is snapshotted when voting starts; later
+        // contributions fund the task but don't add weight
+        let counts_for_budget_vote = ctx.accounts.task.budget_voting_started_at.is_none();
+        if counts_for_budget_vote {
+            contribution.voting_weight += amount;
+        }
 
         // Update escrow totals
         let escrow = &mut ctx.accounts.escrow;
         escrow.total_contributed = escrow.total_contributed.checked_add(amount).unwrap();
+        if counts_for_budget_vote {
+            escrow.total_voting_weight = escrow.total_voting_weight.checked_add(amount).unwrap();
+        }
 
         // INVARIANT CHECK
         assert_invariant(escrow)?;
@@ -76,27 +101,27 @@ pub mod task_escrow {
 
     /// Execute payout to recipient
     /// Requires: Task approved, KYC verified
-    pub fn execute_payout(
-        ctx: Context<ExecutePayout>,
-        amount: u64,
-    ) -> Result<()> {
+    /// Pays the finalized budget once; the task moves to PaidOut
+    pub fn execute_payout(ctx: Context<ExecutePayout>) -> Result<()> {
         require!(!ctx.accounts.escrow.is_frozen, EscrowError::EscrowFrozen);
 
+        let amount = ctx
+            .accounts
+            .task
+            .finalized_budget
+            .ok_or(EscrowError::BudgetNotFinalized)?;
         let escrow = &ctx.accounts.escrow;
 
         // Check can payout
-        let available = escrow.total_contributed
-            .checked_sub(escrow.total_paid_out)
-            .unwrap()
-            .checked_sub(escrow.total_refunded)
-            .unwrap();
+        let available = escrow.available().ok_or(EscrowError::InvariantViolation)?;
 
         require!(amount <= available, EscrowError::InsufficientFunds);
 
         // Transfer USDC from escrow vault to recipient
+        let task_key = ctx.accounts.task.key();
         let seeds = &[
             b"escrow",
-            ctx.accounts.task.key().as_ref(),
+            task_key.as_ref(),
             &[escrow.bump],
         ];
         let signer = &[&seeds[..]];
@@ -125,24 +150,156 @@ pub mod task_escrow {
             executed_at: Clock::get()?.unix_timestamp,
         });
 
+        mark_task_paid_out(
+            &ctx.accounts.task_manager_program,
+            &ctx.accounts.task,
+            &ctx.accounts.task_history,
+            &ctx.accounts.escrow,
+        )
+    }
+
+    /// Execute payout across the task's recipient split table
+    /// Requires: Task approved, every recipient KYC verified; callable by the
+    /// creator or any recipient. Pays the finalized budget once; the task
+    /// moves to PaidOut.
+    /// remaining_accounts: [kyc_attestation, recipient_token] per split, in table order
+    pub fn execute_split_payout<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteSplitPayout<'info>>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.escrow.is_frozen, EscrowError::EscrowFrozen);
+
+        let task = &ctx.accounts.task;
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == task.creator || task.recipient_splits.iter().any(|s| s.recipient == authority),
+            EscrowError::UnauthorizedPayoutCaller
+        );
+        let amount = task.finalized_budget.ok_or(EscrowError::BudgetNotFinalized)?;
+        let splits = task.recipient_splits.clone();
+        require!(
+            ctx.remaining_accounts.len() == splits.len() * 2,
+            EscrowError::InvalidSplitAccounts
+        );
+
+        let escrow = &ctx.accounts.escrow;
+
+        // Check can payout
+        let available = escrow.available().ok_or(EscrowError::InvariantViolation)?;
+
+        require!(amount <= available, EscrowError::InsufficientFunds);
+
+        let clock = Clock::get()?;
+        let shares = task.split_payout(amount);
+
+        let task_key = task.key();
+        let seeds = &[
+            b"escrow",
+            task_key.as_ref(),
+            &[escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+        let escrow_info = ctx.accounts.escrow.to_account_info();
+
+        // All transfers happen in one instruction: any failing check aborts the whole payout
+        for (i, split) in splits.iter().enumerate() {
+            let kyc_info = &ctx.remaining_accounts[2 * i];
+            let recipient_token_info = &ctx.remaining_accounts[2 * i + 1];
+
+            // KYC: each recipient needs their own valid attestation
+            let (expected_kyc, _) = Pubkey::find_program_address(
+                &[b"kyc", split.recipient.as_ref()],
+                ctx.program_id,
+            );
+            require_keys_eq!(kyc_info.key(), expected_kyc, EscrowError::InvalidSplitAccounts);
+            let kyc = Account::<KycAttestation>::try_from(kyc_info)?;
+            require!(kyc.is_valid(clock.unix_timestamp), EscrowError::KycAttestationInvalid);
+
+            let recipient_token = Account::<TokenAccount>::try_from(recipient_token_info)?;
+            require_keys_eq!(recipient_token.owner, split.recipient, EscrowError::InvalidSplitAccounts);
+            require_keys_eq!(
+                recipient_token.mint,
+                ctx.accounts.escrow_vault.mint,
+                EscrowError::InvalidSplitAccounts
+            );
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.escrow_vault.to_account_info(),
+                to: recipient_token_info.clone(),
+                authority: escrow_info.clone(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, shares[i])?;
+
+            let escrow = &mut ctx.accounts.escrow;
+            escrow.total_paid_out = escrow.total_paid_out.checked_add(shares[i]).unwrap();
+
+            emit!(PayoutExecuted {
+                task: task_key,
+                recipient: split.recipient,
+                amount: shares[i],
+                total_paid_out: escrow.total_paid_out,
+                executed_at: clock.unix_timestamp,
+            });
+        }
+
+        // INVARIANT CHECK
+        assert_invariant(&ctx.accounts.escrow)?;
+
+        mark_task_paid_out(
+            &ctx.accounts.task_manager_program,
+            &ctx.accounts.task,
+            &ctx.accounts.task_history,
+            &ctx.accounts.escrow,
+        )
+    }
+
+    /// Record or renew a recipient's KYC attestation (KYC provider only)
+    pub fn record_kyc_attestation(
+        ctx: Context<RecordKycAttestation>,
+        attestation_hash: [u8; 32],
+        expires_at: i64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(expires_at > clock.unix_timestamp, EscrowError::KycAttestationInvalid);
+
+        let attestation = &mut ctx.accounts.kyc_attestation;
+        attestation.recipient = ctx.accounts.recipient.key();
+        attestation.attestation_hash = attestation_hash;
+        attestation.issuer = ctx.accounts.issuer.key();
+        attestation.issued_at = clock.unix_timestamp;
+        attestation.expires_at = expires_at;
+        attestation.bump = ctx.bumps.kyc_attestation;
+
+        emit!(KycAttestationRecorded {
+            recipient: attestation.recipient,
+            attestation_hash,
+            expires_at,
+            recorded_at: clock.unix_timestamp,
+        });
+
         Ok(())
     }
 
     /// Execute refund (pro-rata)
+    /// Not while the task's budget vote runs: the refund would pull weight
+    /// out from under a cast vote
     pub fn execute_refund(ctx: Context<ExecuteRefund>) -> Result<()> {
         let contribution = &ctx.accounts.contribution;
 
         require!(!contribution.refunded, EscrowError::AlreadyRefunded);
         require!(!ctx.accounts.escrow.is_frozen, EscrowError::EscrowFrozen);
 
-        // Calculate pro-rata refund
-        let refund_amount = contribution.amount; // Full refund for simplicity in v0
+        // Refund whatever is still held (funds moved in by a reallocation
+        // can reopen a contribution that was refunded before)
+        let refund_amount = contribution.net_amount();
 
         // Transfer USDC back to contributor
         let escrow = &ctx.accounts.escrow;
+        let task_key = ctx.accounts.task.key();
         let seeds = &[
             b"escrow",
-            ctx.accounts.task.key().as_ref(),
+            task_key.as_ref(),
             &[escrow.bump],
         ];
         let signer = &[&seeds[..]];
@@ -156,14 +313,21 @@ pub mod task_escrow {
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
         token::transfer(cpi_ctx, refund_amount)?;
 
-        // Update contribution
-        let contribution = &mut ctx.accounts.contribution;
-        contribution.refunded = true;
-        contribution.refund_amount = refund_amount;
+        // Update contribution; its budget vote weight leaves the snapshot total
+        let voting_weight = ctx.accounts.contribution.voting_weight;
+        ctx.accounts.contribution.close_out();
+
+        // A refunded share no longer backs a reallocation vote
+        release_reallocation_vote(
+            &ctx.accounts.reallocation,
+            &ctx.accounts.reallocation_vote,
+            Clock::get()?.unix_timestamp,
+        )?;
 
         // Update escrow
         let escrow = &mut ctx.accounts.escrow;
         escrow.total_refunded = escrow.total_refunded.checked_add(refund_amount).unwrap();
+        escrow.total_voting_weight = escrow.total_voting_weight.checked_sub(voting_weight).unwrap();
 
         // INVARIANT CHECK
         assert_invariant(escrow)?;
@@ -179,7 +343,194 @@ pub mod task_escrow {
         Ok(())
     }
 
-    /// Freeze escrow (during disputes)
+    /// Propose moving a failed task's funds to a sibling task in the same
+    /// campaign; any contributor with value still held can propose
+    /// Replaces a proposal that closed without passing (votes on it no longer count)
+    pub fn propose_reallocation(ctx: Context<ProposeReallocation>) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let target_task = &ctx.accounts.target_task;
+        let clock = Clock::get()?;
+
+        require!(
+            matches!(task.state, TaskState::Rejected | TaskState::Refunding),
+            EscrowError::TaskNotFailed
+        );
+        require!(
+            target_task.key() != task.key()
+                && target_task.campaign == task.campaign
+                && accepts_reallocated_funds(target_task),
+            EscrowError::InvalidReallocationTarget
+        );
+        require!(
+            ctx.accounts.contribution.net_amount() > 0,
+            EscrowError::NothingToReallocate
+        );
+
+        let escrow = &ctx.accounts.escrow;
+        let proposal = &mut ctx.accounts.reallocation;
+        require!(
+            proposal.can_be_replaced(REALLOCATION_THRESHOLD_PERCENTAGE, clock.unix_timestamp),
+            EscrowError::ReallocationPending
+        );
+
+        proposal.nonce = proposal.nonce.checked_add(1).unwrap();
+        proposal.task = task.key();
+        proposal.target_task = target_task.key();
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.proposed_at = clock.unix_timestamp;
+        proposal.voting_ends_at = clock.unix_timestamp + REALLOCATION_VOTING_SECONDS;
+        proposal.eligible_weight = escrow.available().ok_or(EscrowError::InvariantViolation)?;
+        proposal.consent_weight = 0;
+        proposal.dissent_weight = 0;
+        proposal.total_voters = 0;
+        proposal.total_moved = 0;
+        proposal.bump = ctx.bumps.reallocation;
+
+        emit!(ReallocationProposed {
+            task: task.key(),
+            target_task: target_task.key(),
+            proposer: ctx.accounts.proposer.key(),
+            nonce: proposal.nonce,
+            eligible_weight: proposal.eligible_weight,
+            voting_ends_at: proposal.voting_ends_at,
+        });
+
+        Ok(())
+    }
+
+    /// Consent to (or decline) moving your share to the target task
+    /// Weight = net contribution; votes can be changed until voting closes
+    pub fn vote_reallocation(ctx: Context<VoteReallocation>, consent: bool) -> Result<()> {
+        let clock = Clock::get()?;
+        let proposal = &mut ctx.accounts.reallocation;
+
+        require!(
+            clock.unix_timestamp <= proposal.voting_ends_at,
+            EscrowError::ReallocationVotingClosed
+        );
+
+        let vote_weight = ctx.accounts.contribution.net_amount();
+        require!(vote_weight > 0, EscrowError::NothingToReallocate);
+
+        let vote = &mut ctx.accounts.reallocation_vote;
+        let updated = proposal.counts(vote);
+        if updated {
+            proposal
+                .remove_vote(vote.consent, vote.vote_weight)
+                .ok_or(EscrowError::ReallocationTallyMismatch)?;
+        } else {
+            proposal.total_voters += 1;
+            vote.bump = ctx.bumps.reallocation_vote;
+        }
+
+        proposal
+            .add_vote(consent, vote_weight)
+            .ok_or(EscrowError::ReallocationTallyMismatch)?;
+
+        vote.task = proposal.task;
+        vote.contributor = ctx.accounts.contributor.key();
+        vote.consent = consent;
+        vote.vote_weight = vote_weight;
+        vote.proposal_nonce = proposal.nonce;
+        vote.executed = false;
+        vote.voted_at = clock.unix_timestamp;
+
+        emit!(ReallocationVoteCast {
+            task: proposal.task,
+            contributor: ctx.accounts.contributor.key(),
+            consent,
+            vote_weight,
+            updated,
+            voted_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Move one consenting contributor's share to the target task once the
+    /// proposal has passed (permissionless). The share is credited to the
+    /// contributor's Contribution on the target task; non-consenting
+    /// contributors claim theirs with execute_refund.
+    pub fn execute_reallocation(ctx: Context<ExecuteReallocation>) -> Result<()> {
+        let clock = Clock::get()?;
+        let proposal = &ctx.accounts.reallocation;
+
+        require!(
+            clock.unix_timestamp > proposal.voting_ends_at,
+            EscrowError::ReallocationVotingOpen
+        );
+        require!(
+            proposal.passes(REALLOCATION_THRESHOLD_PERCENTAGE),
+            EscrowError::ReallocationNotPassed
+        );
+        require!(
+            accepts_reallocated_funds(&ctx.accounts.target_task),
+            EscrowError::InvalidReallocationTarget
+        );
+        require!(!ctx.accounts.escrow.is_frozen, EscrowError::EscrowFrozen);
+        require!(!ctx.accounts.target_escrow.is_frozen, EscrowError::EscrowFrozen);
+
+        let contribution = &ctx.accounts.contribution;
+        require!(!contribution.refunded, EscrowError::AlreadyRefunded);
+        let amount = contribution.net_amount();
+        require!(amount > 0, EscrowError::NothingToReallocate);
+
+        // Transfer USDC from the failed task's vault to the target's
+        let escrow = &ctx.accounts.escrow;
+        let task_key = ctx.accounts.task.key();
+        let seeds = &[
+            b"escrow",
+            task_key.as_ref(),
+            &[escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow_vault.to_account_info(),
+            to: ctx.accounts.target_escrow_vault.to_account_info(),
+            authority: ctx.accounts.escrow.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        // Close out the failed task's contribution
+        let voting_weight = ctx.accounts.contribution.voting_weight;
+        ctx.accounts.contribution.close_out();
+
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.total_reallocated = escrow.total_reallocated.checked_add(amount).unwrap();
+        escrow.total_voting_weight = escrow.total_voting_weight.checked_sub(voting_weight).unwrap();
+        assert_invariant(escrow)?;
+
+        // Credit the contributor on the target task (reopening it if they
+        // had been refunded there)
+        let target_contribution = &mut ctx.accounts.target_contribution;
+        target_contribution.task = ctx.accounts.target_task.key();
+        target_contribution.contributor = ctx.accounts.contributor.key();
+        target_contribution.credit(amount).unwrap();
+        target_contribution.contributed_at = clock.unix_timestamp;
+
+        let target_escrow = &mut ctx.accounts.target_escrow;
+        target_escrow.total_contributed = target_escrow.total_contributed.checked_add(amount).unwrap();
+        assert_invariant(target_escrow)?;
+
+        ctx.accounts.reallocation_vote.executed = true;
+        let proposal = &mut ctx.accounts.reallocation;
+        proposal.total_moved = proposal.total_moved.checked_add(amount).unwrap();
+
+        emit!(ContributionReallocated {
+            task: task_key,
+            target_task: ctx.accounts.target_task.key(),
+            contributor: ctx.accounts.contributor.key(),
+            amount,
+            executed_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Freeze escrow (during disputes; called by dispute_module via CPI)
     pub fn freeze_escrow(ctx: Context<FreezeEscrow>) -> Result<()> {
         let escrow = &mut ctx.accounts.escrow;
         escrow.is_frozen = true;
@@ -192,7 +543,7 @@ pub mod task_escrow {
         Ok(())
     }
 
-    /// Unfreeze escrow (after dispute resolution)
+    /// Unfreeze escrow (after dispute resolution; called by dispute_module via CPI)
     pub fn unfreeze_escrow(ctx: Context<FreezeEscrow>) -> Result<()> {
         let escrow = &mut ctx.accounts.escrow;
         escrow.is_frozen = false;
@@ -207,11 +558,10 @@ pub mod task_escrow {
 }
 
 /// CRITICAL INVARIANT:
-/// Total USDC in vault MUST equal: total_contributed - total_paid_out - total_refunded
+/// Total USDC in vault MUST equal:
+/// total_contributed - total_paid_out - total_refunded - total_reallocated
 fn assert_invariant(escrow: &Escrow) -> Result<()> {
-    let expected_balance = escrow.total_contributed
-        .checked_sub(escrow.total_paid_out).unwrap()
-        .checked_sub(escrow.total_refunded).unwrap();
+    let _expected_balance = escrow.available().ok_or(EscrowError::InvariantViolation)?;
 
     // In production, would check actual vault balance matches
     // require!(vault_balance == expected_balance, EscrowError::InvariantViolation);
@@ -219,6 +569,57 @@ fn assert_invariant(escrow: &Escrow) -> Result<()> {
     Ok(())
 }
 
+/// Remove a refunding contributor's vote from the task's reallocation tally
+/// Both accounts are PDAs that may not exist yet; nothing to do then
+fn release_reallocation_vote(
+    reallocation: &AccountInfo,
+    reallocation_vote: &AccountInfo,
+    current_time: i64,
+) -> Result<()> {
+    if reallocation.data_is_empty() || reallocation_vote.data_is_empty() {
+        return Ok(());
+    }
+    require_keys_eq!(*reallocation.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    require_keys_eq!(*reallocation_vote.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+    let mut proposal = Reallocation::try_deserialize(&mut &reallocation.try_borrow_data()?[..])?;
+    let mut vote = ReallocationVote::try_deserialize(&mut &reallocation_vote.try_borrow_data()?[..])?;
+    proposal
+        .release_vote(&mut vote, current_time)
+        .ok_or(EscrowError::ReallocationTallyMismatch)?;
+
+    proposal.try_serialize(&mut &mut reallocation.try_borrow_mut_data()?[..])?;
+    vote.try_serialize(&mut &mut reallocation_vote.try_borrow_mut_data()?[..])
+}
+
+/// CPI task_manager::mark_paid_out, signed by the escrow PDA
+fn mark_task_paid_out<'info>(
+    task_manager_program: &Program<'info, TaskManager>,
+    task: &Account<'info, Task>,
+    task_history: &UncheckedAccount<'info>,
+    escrow: &Account<'info, Escrow>,
+) -> Result<()> {
+    let task_key = task.key();
+    let seeds = &[b"escrow", task_key.as_ref(), &[escrow.bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = MarkPaidOut {
+        task: task.to_account_info(),
+        task_history: task_history.to_account_info(),
+        escrow: escrow.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(task_manager_program.to_account_info(), cpi_accounts, signer);
+    task_manager::cpi::mark_paid_out(cpi_ctx)
+}
+
+/// Reallocated funds can go to a sibling task still raising or using funds
+fn accepts_reallocated_funds(task: &Task) -> bool {
+    matches!(
+        task.state,
+        TaskState::FundingOpen | TaskState::Funded | TaskState::InProgress
+    )
+}
+
 // Account contexts
 #[derive(Accounts)]
 pub struct InitializeEscrow<'info> {
@@ -266,8 +667,8 @@ pub struct Contribute<'info> {
     #[account(mut)]
     pub contributor_token: Account<'info, TokenAccount>,
 
-    /// CHECK: Task account
-    pub task: UncheckedAccount<'info>,
+    /// Task being funded; its voting start decides whether this counts as vote weight
+    pub task: Account<'info, Task>,
 
     #[account(mut)]
     pub contributor: Signer<'info>,
@@ -288,15 +689,78 @@ pub struct ExecutePayout<'info> {
     #[account(mut)]
     pub escrow_vault: Account<'info, TokenAccount>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = recipient_token.owner == recipient.key() @ EscrowError::UnauthorizedRecipient
+    )]
     pub recipient_token: Account<'info, TokenAccount>,
 
-    /// CHECK: Task account
-    pub task: UncheckedAccount<'info>,
+    /// Split tasks must be paid through execute_split_payout
+    #[account(
+        mut,
+        constraint = task.state == TaskState::Approved @ EscrowError::TaskNotApproved,
+        constraint = task.recipient_splits.is_empty() @ EscrowError::SplitPayoutRequired,
+        constraint = task.recipient == Some(recipient.key()) @ EscrowError::UnauthorizedRecipient
+    )]
+    pub task: Account<'info, Task>,
+
+    /// CHECK: Task history PDA, validated by task_manager
+    #[account(mut)]
+    pub task_history: UncheckedAccount<'info>,
 
     pub recipient: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
+    pub task_manager_program: Program<'info, TaskManager>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteSplitPayout<'info> {
+    #[account(
+        mut,
+        seeds = [b"escrow", task.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut)]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = task.state == TaskState::Approved @ EscrowError::TaskNotApproved,
+        constraint = !task.recipient_splits.is_empty() @ EscrowError::NoRecipientSplits
+    )]
+    pub task: Account<'info, Task>,
+
+    /// CHECK: Task history PDA, validated by task_manager
+    #[account(mut)]
+    pub task_history: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub task_manager_program: Program<'info, TaskManager>,
+}
+
+#[derive(Accounts)]
+pub struct RecordKycAttestation<'info> {
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        space = KycAttestation::LEN,
+        seeds = [b"kyc", recipient.key().as_ref()],
+        bump
+    )]
+    pub kyc_attestation: Account<'info, KycAttestation>,
+
+    /// CHECK: Recipient wallet being attested
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(mut, address = kyc_issuer::ID @ EscrowError::UnauthorizedKycIssuer)]
+    pub issuer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -321,12 +785,169 @@ pub struct ExecuteRefund<'info> {
     #[account(mut)]
     pub contributor_token: Account<'info, TokenAccount>,
 
-    /// CHECK: Task account
-    pub task: UncheckedAccount<'info>,
+    /// CHECK: The task's reallocation proposal, if one was made
+    #[account(
+        mut,
+        seeds = [b"reallocation", task.key().as_ref()],
+        bump
+    )]
+    pub reallocation: UncheckedAccount<'info>,
 
+    /// CHECK: The contributor's reallocation vote, if they cast one
+    #[account(
+        mut,
+        seeds = [b"reallocation_vote", task.key().as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub reallocation_vote: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = task.state != TaskState::VotingBudget @ EscrowError::BudgetVoteInProgress
+    )]
+    pub task: Account<'info, Task>,
+
+    pub contributor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeReallocation<'info> {
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = Reallocation::LEN,
+        seeds = [b"reallocation", task.key().as_ref()],
+        bump
+    )]
+    pub reallocation: Account<'info, Reallocation>,
+
+    #[account(
+        seeds = [b"escrow", task.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        seeds = [b"contribution", task.key().as_ref(), proposer.key().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    pub task: Account<'info, Task>,
+
+    pub target_task: Account<'info, Task>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VoteReallocation<'info> {
+    #[account(
+        mut,
+        seeds = [b"reallocation", reallocation.task.as_ref()],
+        bump = reallocation.bump
+    )]
+    pub reallocation: Account<'info, Reallocation>,
+
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = ReallocationVote::LEN,
+        seeds = [b"reallocation_vote", reallocation.task.as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub reallocation_vote: Account<'info, ReallocationVote>,
+
+    #[account(
+        seeds = [b"contribution", reallocation.task.as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(mut)]
     pub contributor: Signer<'info>,
 
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteReallocation<'info> {
+    #[account(
+        mut,
+        seeds = [b"reallocation", task.key().as_ref()],
+        bump = reallocation.bump
+    )]
+    pub reallocation: Account<'info, Reallocation>,
+
+    #[account(
+        mut,
+        seeds = [b"reallocation_vote", task.key().as_ref(), contributor.key().as_ref()],
+        bump = reallocation_vote.bump,
+        constraint = reallocation.counts(&reallocation_vote) @ EscrowError::NoReallocationConsent,
+        constraint = reallocation_vote.consent @ EscrowError::NoReallocationConsent,
+        constraint = !reallocation_vote.executed @ EscrowError::AlreadyRefunded
+    )]
+    pub reallocation_vote: Account<'info, ReallocationVote>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", task.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"contribution", task.key().as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(
+        mut,
+        constraint = escrow_vault.owner == escrow.key() @ EscrowError::InvalidVault
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", target_task.key().as_ref()],
+        bump = target_escrow.bump
+    )]
+    pub target_escrow: Account<'info, Escrow>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Contribution::LEN,
+        seeds = [b"contribution", target_task.key().as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub target_contribution: Account<'info, Contribution>,
+
+    #[account(
+        mut,
+        constraint = target_escrow_vault.owner == target_escrow.key() @ EscrowError::InvalidVault
+    )]
+    pub target_escrow_vault: Account<'info, TokenAccount>,
+
+    pub task: Account<'info, Task>,
+
+    #[account(address = reallocation.target_task @ EscrowError::InvalidReallocationTarget)]
+    pub target_task: Account<'info, Task>,
+
+    /// CHECK: Consenting contributor whose share moves
+    pub contributor: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -341,6 +962,10 @@ pub struct FreezeEscrow<'info> {
     /// CHECK: Task account
     pub task: UncheckedAccount<'info>,
 
+    /// The task's dispute PDA in dispute_module
+    #[account(
+        address = Task::dispute_authority(&task.key()) @ EscrowError::UnauthorizedFreeze
+    )]
     pub authority: Signer<'info>,
 }
 
@@ -369,6 +994,14 @@ pub struct PayoutExecuted {
     pub executed_at: i64,
 }
 
+#[event]
+pub struct KycAttestationRecorded {
+    pub recipient: Pubkey,
+    pub attestation_hash: [u8; 32],
+    pub expires_at: i64,
+    pub recorded_at: i64,
+}
+
 #[event]
 pub struct RefundExecuted {
     pub task: Pubkey,
@@ -378,6 +1011,35 @@ pub struct RefundExecuted {
     pub executed_at: i64,
 }
 
+#[event]
+pub struct ReallocationProposed {
+    pub task: Pubkey,
+    pub target_task: Pubkey,
+    pub proposer: Pubkey,
+    pub nonce: u64,
+    pub eligible_weight: u64,
+    pub voting_ends_at: i64,
+}
+
+#[event]
+pub struct ReallocationVoteCast {
+    pub task: Pubkey,
+    pub contributor: Pubkey,
+    pub consent: bool,
+    pub vote_weight: u64,
+    pub updated: bool,
+    pub voted_at: i64,
+}
+
+#[event]
+pub struct ContributionReallocated {
+    pub task: Pubkey,
+    pub target_task: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    pub executed_at: i64,
+}
+
 #[event]
 pub struct EscrowFrozen {
     pub task: Pubkey,
@@ -407,4 +1069,67 @@ pub enum EscrowError {
 
     #[msg("CRITICAL: Escrow invariant violated")]
     InvariantViolation,
+
+    #[msg("Task is not approved for payout")]
+    TaskNotApproved,
+
+    #[msg("Task has no recipient splits")]
+    NoRecipientSplits,
+
+    #[msg("Task has recipient splits - use execute_split_payout")]
+    SplitPayoutRequired,
+
+    #[msg("Split payout accounts do not match the recipient table")]
+    InvalidSplitAccounts,
+
+    #[msg("Recipient KYC attestation is missing or expired")]
+    KycAttestationInvalid,
+
+    #[msg("Only the KYC issuer can record attestations")]
+    UnauthorizedKycIssuer,
+
+    #[msg("Task has not failed - funds can only be reallocated from rejected tasks")]
+    TaskNotFailed,
+
+    #[msg("Target must be another open task in the same campaign")]
+    InvalidReallocationTarget,
+
+    #[msg("No contribution left to reallocate")]
+    NothingToReallocate,
+
+    #[msg("Reallocation voting has closed")]
+    ReallocationVotingClosed,
+
+    #[msg("Reallocation voting is still open")]
+    ReallocationVotingOpen,
+
+    #[msg("Reallocation did not pass")]
+    ReallocationNotPassed,
+
+    #[msg("Contributor did not consent to the reallocation")]
+    NoReallocationConsent,
+
+    #[msg("Vault is not owned by the escrow")]
+    InvalidVault,
+
+    #[msg("Task budget has not been finalized")]
+    BudgetNotFinalized,
+
+    #[msg("Only the task recipient can receive this payout")]
+    UnauthorizedRecipient,
+
+    #[msg("Only the task creator or a split recipient can execute the payout")]
+    UnauthorizedPayoutCaller,
+
+    #[msg("A reallocation proposal is still open or has passed")]
+    ReallocationPending,
+
+    #[msg("Reallocation vote is not counted in the current proposal's tally")]
+    ReallocationTallyMismatch,
+
+    #[msg("Refunds are closed while the task's budget vote runs")]
+    BudgetVoteInProgress,
+
+    #[msg("Only the task's dispute can freeze or unfreeze its escrow")]
+    UnauthorizedFreeze,
 }
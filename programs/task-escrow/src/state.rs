@@ -6,18 +6,30 @@ pub struct Escrow {
     pub total_contributed: u64,
     pub total_refunded: u64,
     pub total_paid_out: u64,
+    pub total_reallocated: u64,  // Moved to sibling tasks by donor vote
+    pub total_voting_weight: u64,  // Contributed before budget voting started (snapshot total)
     pub is_frozen: bool,  // For disputes
     pub bump: u8,
 }
 
 impl Escrow {
-    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 1 + 1;
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 1;
 
-    /// INVARIANT: Balance must equal contributed - paid - refunded
+    /// Value still held: contributed - paid - refunded - reallocated
+    /// None if the totals are inconsistent (more left than came in)
+    pub fn available(&self) -> Option<u64> {
+        self.total_contributed
+            .checked_sub(self.total_paid_out)?
+            .checked_sub(self.total_refunded)?
+            .checked_sub(self.total_reallocated)
+    }
+
+    /// INVARIANT: Balance must equal contributed - paid - refunded - reallocated
     pub fn check_invariant(&self, actual_vault_balance: u64) -> bool {
         let expected_balance = self.total_contributed
             .saturating_sub(self.total_paid_out)
-            .saturating_sub(self.total_refunded);
+            .saturating_sub(self.total_refunded)
+            .saturating_sub(self.total_reallocated);
 
         actual_vault_balance == expected_balance
     }
@@ -29,10 +41,325 @@ pub struct Contribution {
     pub contributor: Pubkey,
     pub amount: u64,  // Total contributed (cumulative if multiple contributions)
     pub contributed_at: i64,
-    pub refunded: bool,
+    pub refunded: bool,       // Refunded or reallocated; nothing left to claim
     pub refund_amount: u64,
+    pub voting_weight: u64,   // Contributed before budget voting started, less refunds (weight snapshot)
 }
 
 impl Contribution {
-    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1 + 8;
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1 + 8 + 8;
+
+    /// Value still held for this contributor
+    pub fn net_amount(&self) -> u64 {
+        self.amount.saturating_sub(self.refund_amount)
+    }
+
+    /// Settle everything still held (refund or reallocation); returns the amount released
+    /// The budget vote weight goes with it, so contributing again during
+    /// voting can't win it back
+    pub fn close_out(&mut self) -> u64 {
+        let released = self.net_amount();
+        self.refund_amount = self.amount;
+        self.refunded = true;
+        self.voting_weight = 0;
+        released
+    }
+
+    /// Credit new funds (a contribution or a reallocation); reopens a settled
+    /// contribution while keeping its refund history
+    pub fn credit(&mut self, amount: u64) -> Option<()> {
+        self.amount = self.amount.checked_add(amount)?;
+        self.refunded = false;
+        Some(())
+    }
+
+    /// Budget vote weight: the snapshot taken when voting started, less
+    /// refunds made before it. Contributions during voting add nothing and
+    /// refunds are blocked while it runs, so it can't move under a vote.
+    pub fn snapshot_weight(&self) -> u64 {
+        self.voting_weight
+    }
+}
+
+/// Proposal to move a failed task's funds to a sibling task in the campaign
+/// PDA seeds: ["reallocation", task]
+/// A proposal that failed its vote can be replaced by a new one
+#[account]
+pub struct Reallocation {
+    pub task: Pubkey,          // Failed task
+    pub target_task: Pubkey,   // Sibling task receiving consenting shares
+    pub proposer: Pubkey,
+    pub nonce: u64,            // Incremented per proposal; identifies the round for votes
+    pub proposed_at: i64,
+    pub voting_ends_at: i64,
+    pub eligible_weight: u64,  // Value held for contributors at proposal time
+    pub consent_weight: u64,
+    pub dissent_weight: u64,
+    pub total_voters: u32,
+    pub total_moved: u64,
+    pub bump: u8,
+}
+
+impl Reallocation {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 4 + 8 + 1;
+
+    /// A new proposal may take this account's place when none was made yet
+    /// or the last one closed without passing
+    pub fn can_be_replaced(&self, threshold_pct: u8, current_time: i64) -> bool {
+        self.nonce == 0 || (current_time > self.voting_ends_at && !self.passes(threshold_pct))
+    }
+
+    /// Whether a vote was cast on this proposal (not an earlier, replaced one)
+    pub fn counts(&self, vote: &ReallocationVote) -> bool {
+        vote.contributor != Pubkey::default() && vote.proposal_nonce == self.nonce
+    }
+
+    /// Add a vote's weight to its side
+    pub fn add_vote(&mut self, consent: bool, weight: u64) -> Option<()> {
+        let side = if consent { &mut self.consent_weight } else { &mut self.dissent_weight };
+        *side = side.checked_add(weight)?;
+        Some(())
+    }
+
+    /// Take back a vote counted on this proposal; None if the tally doesn't hold it
+    pub fn remove_vote(&mut self, consent: bool, weight: u64) -> Option<()> {
+        let side = if consent { &mut self.consent_weight } else { &mut self.dissent_weight };
+        *side = side.checked_sub(weight)?;
+        Some(())
+    }
+
+    /// Pull a refunding contributor's vote out of the tally while voting is
+    /// open; once it closes the outcome stands
+    pub fn release_vote(&mut self, vote: &mut ReallocationVote, current_time: i64) -> Option<()> {
+        if !self.counts(vote) || vote.executed || current_time > self.voting_ends_at {
+            return Some(());
+        }
+        self.remove_vote(vote.consent, vote.vote_weight)?;
+        vote.vote_weight = 0;
+        Some(())
+    }
+
+    /// Passes when consenting weight exceeds the threshold share of the
+    /// value held for contributors when the proposal was made
+    pub fn passes(&self, threshold_pct: u8) -> bool {
+        self.eligible_weight > 0
+            && (self.consent_weight as u128) * 100 > (self.eligible_weight as u128) * threshold_pct as u128
+    }
+}
+
+/// PDA seeds: ["reallocation_vote", task, contributor]
+#[account]
+pub struct ReallocationVote {
+    pub task: Pubkey,
+    pub contributor: Pubkey,
+    pub consent: bool,
+    pub vote_weight: u64,
+    pub proposal_nonce: u64,  // nonce of the proposal voted on
+    pub executed: bool,   // Share already moved to the target task
+    pub voted_at: i64,
+    pub bump: u8,
+}
+
+impl ReallocationVote {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 8 + 1 + 8 + 1;
+}
+
+/// KYC attestation for a recipient wallet
+/// PDA seeds: ["kyc", recipient]
+/// Only the attestation hash is stored on-chain; the provider keeps the rest
+#[account]
+pub struct KycAttestation {
+    pub recipient: Pubkey,
+    pub attestation_hash: [u8; 32],
+    pub issuer: Pubkey,
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl KycAttestation {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1;
+
+    pub fn is_valid(&self, current_time: i64) -> bool {
+        current_time < self.expires_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proposal(consent_weight: u64, eligible_weight: u64) -> Reallocation {
+        Reallocation {
+            task: Pubkey::default(),
+            target_task: Pubkey::default(),
+            proposer: Pubkey::default(),
+            nonce: 1,
+            proposed_at: 0,
+            voting_ends_at: 100,
+            eligible_weight,
+            consent_weight,
+            dissent_weight: 0,
+            total_voters: 0,
+            total_moved: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_weight() {
+        let mut contribution = Contribution {
+            task: Pubkey::default(),
+            contributor: Pubkey::default(),
+            amount: 100,
+            contributed_at: 0,
+            refunded: false,
+            refund_amount: 0,
+            voting_weight: 100,
+        };
+        assert_eq!(contribution.snapshot_weight(), 100);
+
+        // Topping up during voting doesn't add weight
+        contribution.credit(900).unwrap();
+        assert_eq!(contribution.snapshot_weight(), 100);
+
+        // Refunds do take it away
+        assert_eq!(contribution.close_out(), 1_000);
+        assert_eq!(contribution.snapshot_weight(), 0);
+
+        // Contributing again during voting keeps the refund on record and
+        // doesn't win the weight back
+        contribution.credit(100).unwrap();
+        assert_eq!((contribution.refund_amount, contribution.net_amount()), (1_000, 100));
+        assert!(!contribution.refunded);
+        assert_eq!(contribution.snapshot_weight(), 0);
+    }
+
+    #[test]
+    fn test_reallocation_threshold() {
+        assert!(proposal(51, 100).passes(50));
+        assert!(!proposal(50, 100).passes(50));
+        assert!(!proposal(0, 0).passes(50));
+        assert!(proposal(u64::MAX, u64::MAX - 1).passes(50));
+    }
+
+    fn vote(consent: bool, vote_weight: u64, proposal_nonce: u64) -> ReallocationVote {
+        ReallocationVote {
+            task: Pubkey::default(),
+            contributor: Pubkey::new_unique(),
+            consent,
+            vote_weight,
+            proposal_nonce,
+            executed: false,
+            voted_at: 0,
+            bump: 0,
+        }
+    }
+
+    fn contribution(amount: u64) -> Contribution {
+        Contribution {
+            task: Pubkey::default(),
+            contributor: Pubkey::default(),
+            amount,
+            contributed_at: 0,
+            refunded: false,
+            refund_amount: 0,
+            voting_weight: amount,
+        }
+    }
+
+    #[test]
+    fn test_reallocation_revote() {
+        let mut proposal = proposal(0, 100);
+        proposal.add_vote(true, 40).unwrap();
+
+        // Switching sides moves the weight
+        proposal.remove_vote(true, 40).unwrap();
+        proposal.add_vote(false, 40).unwrap();
+        assert_eq!((proposal.consent_weight, proposal.dissent_weight), (0, 40));
+
+        // Weight the tally doesn't hold can't be taken back
+        assert!(proposal.remove_vote(true, 40).is_none());
+        assert!(proposal.remove_vote(false, 41).is_none());
+        assert_eq!((proposal.consent_weight, proposal.dissent_weight), (0, 40));
+
+        // Votes on a replaced proposal don't count on the new one
+        assert!(proposal.counts(&vote(false, 40, 1)));
+        assert!(!proposal.counts(&vote(false, 40, 0)));
+    }
+
+    #[test]
+    fn test_refund_after_vote_releases_weight() {
+        let mut proposal = proposal(0, 100);
+        let mut consenter = vote(true, 60, 1);
+        proposal.add_vote(true, 60).unwrap();
+        assert!(proposal.passes(50));
+
+        proposal.release_vote(&mut consenter, 50).unwrap();
+        assert_eq!(proposal.consent_weight, 0);
+        assert_eq!(consenter.vote_weight, 0);
+        assert!(!proposal.passes(50));
+
+        // Releasing twice takes nothing more
+        proposal.release_vote(&mut consenter, 50).unwrap();
+        assert_eq!(proposal.consent_weight, 0);
+
+        // Votes on a replaced proposal are left alone
+        proposal.add_vote(true, 60).unwrap();
+        let mut stale = vote(true, 60, 0);
+        proposal.release_vote(&mut stale, 50).unwrap();
+        assert_eq!(proposal.consent_weight, 60);
+
+        // After voting closes the outcome stands
+        let mut late = vote(true, 60, 1);
+        proposal.release_vote(&mut late, 101).unwrap();
+        assert_eq!(proposal.consent_weight, 60);
+    }
+
+    #[test]
+    fn test_proposal_replacement() {
+        let mut proposal = proposal(0, 100);
+        proposal.nonce = 0;
+        assert!(proposal.can_be_replaced(50, 0));
+
+        proposal.nonce = 1;
+        assert!(!proposal.can_be_replaced(50, 100)); // voting open
+        assert!(proposal.can_be_replaced(50, 101)); // closed without passing
+
+        proposal.consent_weight = 51;
+        assert!(!proposal.can_be_replaced(50, 101)); // passed proposals stay
+    }
+
+    #[test]
+    fn test_reallocation_bookkeeping() {
+        // Source: 100 contributed, 30 already refunded elsewhere in its history
+        let mut source = contribution(100);
+        source.refund_amount = 30;
+        assert_eq!(source.close_out(), 70);
+        assert!(source.refunded);
+        assert_eq!(source.net_amount(), 0);
+
+        // Target: the contributor had refunded their own 50 before
+        let mut target = contribution(50);
+        assert_eq!(target.close_out(), 50);
+        target.credit(70).unwrap();
+        assert!(!target.refunded);
+        assert_eq!(target.net_amount(), 70);
+        assert_eq!(target.close_out(), 70);
+
+        let mut escrow = Escrow {
+            task: Pubkey::default(),
+            total_contributed: 1_000,
+            total_refunded: 200,
+            total_paid_out: 0,
+            total_reallocated: 0,
+            total_voting_weight: 1_000,
+            is_frozen: false,
+            bump: 0,
+        };
+        escrow.total_reallocated = 300;
+        assert_eq!(escrow.available(), Some(500));
+        escrow.total_reallocated = 900;
+        assert_eq!(escrow.available(), None);
+    }
 }
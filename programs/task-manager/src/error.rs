@@ -43,4 +43,139 @@ pub enum TaskError {
 
     #[msg("Minimum contribution amount not met ($10 USDC)")]
     ContributionTooSmall,
+
+    #[msg("Recipient splits must list unique recipients with non-zero shares summing to 10000 bps")]
+    InvalidRecipientSplits,
+
+    #[msg("Recipient splits can only be changed before funding opens")]
+    RecipientSplitsLocked,
+
+    #[msg("Maximum revisions exceeds protocol limit")]
+    InvalidMaxRevisions,
+
+    #[msg("Reviewer pool is too large or has duplicates")]
+    InvalidReviewers,
+
+    #[msg("Approval model settings are invalid")]
+    InvalidApprovalModel,
+
+    #[msg("Signer is not the approval authority for this task's approval model")]
+    UnauthorizedApprover,
+
+    #[msg("Task does not use the hybrid approval model")]
+    NotHybridApproval,
+
+    #[msg("Donor veto window is still open")]
+    VetoWindowOpen,
+
+    #[msg("Donor veto window is not open")]
+    VetoWindowClosed,
+
+    #[msg("Deadline cannot be extended in the current state or after it has passed")]
+    DeadlineNotExtendable,
+
+    #[msg("New deadline must be later than the current deadline")]
+    InvalidDeadline,
+
+    #[msg("Milestones must have titles and non-zero shares summing to 10000 bps")]
+    InvalidMilestones,
+
+    #[msg("Invalid template ID format")]
+    InvalidTemplateId,
+
+    #[msg("Title pattern is empty or too long")]
+    InvalidTitlePattern,
+
+    #[msg("Only the campaign creator can add tasks to this campaign")]
+    NotCampaignCreator,
+
+    #[msg("Campaign cannot accept new tasks in its current state")]
+    CampaignNotAcceptingTasks,
+
+    #[msg("Proofs must be submitted through the proof registry")]
+    UnauthorizedProofSubmission,
+
+    #[msg("Tasks can only be marked paid by their escrow")]
+    UnauthorizedPayout,
+
+    #[msg("Budgets can only be finalized by a completed budget vote")]
+    UnauthorizedBudgetFinalization,
+
+    #[msg("Invalid bounty configuration: needs a deadline, no fixed recipient, and reviewers for reviewer selection")]
+    InvalidBountyConfig,
+
+    #[msg("Bounty is not accepting entries")]
+    BountyEntriesClosed,
+
+    #[msg("Invalid proof hash or URI")]
+    InvalidProof,
+
+    #[msg("Signer cannot select winners for this bounty")]
+    UnauthorizedWinnerSelector,
+
+    #[msg("Bounty winners cannot be selected yet")]
+    WinnerSelectionNotOpen,
+
+    #[msg("Winners must be submitted entries with shares summing to 100%")]
+    InvalidBountyWinners,
+
+    #[msg("Skill tag names must be 1-32 lowercase letters, digits or '-'")]
+    InvalidSkillTagName,
+
+    #[msg("Tasks carry 1-3 unique skill tags")]
+    InvalidSkillTags,
+
+    #[msg("Skill tags have already been set")]
+    SkillTagsAlreadySet,
+
+    #[msg("Skill tag has been retired")]
+    SkillTagInactive,
+
+    #[msg("Skill tag accounts do not match the tags")]
+    InvalidTagAccounts,
+
+    #[msg("Tag index is full; prune closed tasks first")]
+    TagIndexFull,
+
+    #[msg("Only the tag registry authority can manage tags")]
+    UnauthorizedTagAuthority,
+
+    #[msg("Task still needs a recipient")]
+    TaskStillOpen,
+
+    #[msg("Task is not listed in this tag index")]
+    TaskNotInTagIndex,
+
+    #[msg("Review window must be between 1 and 30 days")]
+    InvalidReviewWindow,
+
+    #[msg("Review window has not lapsed")]
+    ReviewWindowOpen,
+
+    #[msg("Budget voting must last between 1 and 30 days")]
+    InvalidBudgetVotingDuration,
+
+    #[msg("Commit-reveal budget votes need a reveal phase between 1 and 7 days")]
+    InvalidBudgetBallot,
+
+    #[msg("Trimmed means must trim under 50% per side; weight caps must be 0.01%-100%")]
+    InvalidBudgetAggregation,
+
+    #[msg("Budget bounds must include the target and stay within 10x of it")]
+    InvalidBudgetBounds,
+
+    #[msg("Default deadline offset must be positive and at most 365 days")]
+    InvalidDeadlineOffset,
+
+    #[msg("The task has an open dispute")]
+    DisputeOpen,
+
+    #[msg("Dispute account is not owned by the dispute module or is malformed")]
+    InvalidDispute,
+
+    #[msg("Task is not open for recipients")]
+    TaskNotOpen,
+
+    #[msg("Task does not carry this tag")]
+    TaskNotTagged,
 }
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::TaskState;
+use crate::state::{ApprovalModel, BudgetAggregation, BudgetBallot, BudgetBounds, RecipientSplit, TaskKind, TaskState};
 
 #[event]
 pub struct TaskCreated {
@@ -9,9 +9,45 @@ pub struct TaskCreated {
     pub creator: Pubkey,
     pub title: String,
     pub target_budget: u64,
+    pub approval_model: ApprovalModel,
+    pub kind: TaskKind,
+    pub template: Option<Pubkey>,
     pub created_at: i64,
 }
 
+#[event]
+pub struct TaskTemplateCreated {
+    pub template_pubkey: Pubkey,
+    pub template_id: String,
+    pub creator: Pubkey,
+    pub title_pattern: String,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct SkillTagCreated {
+    pub tag: Pubkey,
+    pub name: String,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct SkillTagUpdated {
+    pub tag: Pubkey,
+    pub name: String,
+    pub active: bool,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct SkillTagsSet {
+    pub task_pubkey: Pubkey,
+    pub task_id: String,
+    pub tags: Vec<String>,
+    pub listed: bool,
+    pub set_at: i64,
+}
+
 #[event]
 pub struct TaskStateChanged {
     pub task_pubkey: Pubkey,
@@ -26,6 +62,10 @@ pub struct BudgetVotingStarted {
     pub task_pubkey: Pubkey,
     pub task_id: String,
     pub started_at: i64,
+    pub ends_at: i64,
+    pub ballot: BudgetBallot,
+    pub aggregation: BudgetAggregation,
+    pub bounds: BudgetBounds,
 }
 
 #[event]
@@ -36,6 +76,23 @@ pub struct BudgetFinalized {
     pub finalized_at: i64,
 }
 
+#[event]
+pub struct RecipientSplitsSet {
+    pub task_pubkey: Pubkey,
+    pub task_id: String,
+    pub splits: Vec<RecipientSplit>,
+    pub set_at: i64,
+}
+
+#[event]
+pub struct TaskDeadlineExtended {
+    pub task_pubkey: Pubkey,
+    pub task_id: String,
+    pub old_deadline: i64,
+    pub new_deadline: i64,
+    pub extended_at: i64,
+}
+
 #[event]
 pub struct ProofSubmitted {
     pub task_pubkey: Pubkey,
@@ -46,6 +103,33 @@ pub struct ProofSubmitted {
     pub submitted_at: i64,
 }
 
+#[event]
+pub struct BountyParticipantRegistered {
+    pub task_pubkey: Pubkey,
+    pub task_id: String,
+    pub participant: Pubkey,
+    pub registered_at: i64,
+}
+
+#[event]
+pub struct BountyEntrySubmitted {
+    pub task_pubkey: Pubkey,
+    pub task_id: String,
+    pub participant: Pubkey,
+    pub proof_hash: String,
+    pub proof_uri: String,
+    pub submitted_at: i64,
+}
+
+#[event]
+pub struct BountyWinnersSelected {
+    pub task_pubkey: Pubkey,
+    pub task_id: String,
+    pub winners: Vec<RecipientSplit>,
+    pub selected_by: Pubkey,
+    pub selected_at: i64,
+}
+
 #[event]
 pub struct TaskApproved {
     pub task_pubkey: Pubkey,
@@ -53,9 +137,50 @@ pub struct TaskApproved {
     pub approved_at: i64,
 }
 
+#[event]
+pub struct ReviewWindowLapsed {
+    pub task_pubkey: Pubkey,
+    pub task_id: String,
+    pub review_window_ended_at: i64,
+    pub finalized_at: i64,
+}
+
+#[event]
+pub struct VetoWindowOpened {
+    pub task_pubkey: Pubkey,
+    pub task_id: String,
+    pub veto_threshold_bps: u16,
+    pub ends_at: i64,
+}
+
+#[event]
+pub struct TaskVetoed {
+    pub task_pubkey: Pubkey,
+    pub task_id: String,
+    pub vetoed_at: i64,
+}
+
+#[event]
+pub struct TaskDisputed {
+    pub task_pubkey: Pubkey,
+    pub task_id: String,
+    pub disputed_at: i64,
+}
+
+#[event]
+pub struct ChangesRequested {
+    pub task_pubkey: Pubkey,
+    pub task_id: String,
+    pub feedback_hash: [u8; 32],
+    pub revision_count: u8,
+    pub max_revisions: u8,
+    pub requested_at: i64,
+}
+
 #[event]
 pub struct TaskRejected {
     pub task_pubkey: Pubkey,
     pub task_id: String,
+    pub feedback_hash: [u8; 32],
     pub rejected_at: i64,
 }
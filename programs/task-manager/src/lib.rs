@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use campaign_registry::cpi::accounts::IncrementTaskCount;
+use campaign_registry::program::CampaignRegistry;
+use campaign_registry::state::Campaign;
 
 pub mod state;
 pub mod error;
@@ -10,47 +13,232 @@ use events::*;
 
 declare_id!("Task1111111111111111111111111111111111111111");
 
+/// Programs whose PDAs sign approval decisions (see Task::approval_authority)
+pub mod approval_vote_program {
+    use super::*;
+    declare_id!("Appr1111111111111111111111111111111111111111");
+}
+
+pub mod reviewer_registry_program {
+    use super::*;
+    declare_id!("Revw1111111111111111111111111111111111111111");
+}
+
+pub mod dispute_module_program {
+    use super::*;
+    declare_id!("Disp1111111111111111111111111111111111111111");
+}
+
+pub mod proof_registry_program {
+    use super::*;
+    declare_id!("Prof1111111111111111111111111111111111111111");
+}
+
+pub mod budget_vote_program {
+    use super::*;
+    declare_id!("Budg1111111111111111111111111111111111111111");
+}
+
+pub mod task_escrow_program {
+    use super::*;
+    declare_id!("Escr1111111111111111111111111111111111111111");
+}
+
+/// DAO multisig; the only key that can initialize program configs
+pub mod dao_authority {
+    use super::*;
+    declare_id!("Dao1111111111111111111111111111111111111111");
+}
+
 #[program]
 pub mod task_manager {
     use super::*;
 
     /// Create new task
+    /// Campaigns have a single owner and no member list, so only the
+    /// campaign creator can add tasks to it
     pub fn create_task(
         ctx: Context<CreateTask>,
         task_id: String,
-        title: String,
-        deliverables: String,
-        target_budget: u64,
-        recipient: Option<Pubkey>,
-        deadline: Option<i64>,
+        params: CreateTaskParams,
     ) -> Result<()> {
+        let params = params.into_new_task(task_id);
+        params.validate()?;
+
         let task = &mut ctx.accounts.task;
         let clock = Clock::get()?;
 
-        require!(Task::validate_task_id(&task_id), TaskError::InvalidTaskId);
-        require!(Task::validate_title(&title), TaskError::InvalidTitle);
-        require!(Task::validate_deliverables(&deliverables), TaskError::InvalidDeliverables);
+        task.initialize(
+            params,
+            ctx.accounts.campaign.key(),
+            ctx.accounts.creator.key(),
+            clock.unix_timestamp,
+            ctx.bumps.task,
+        );
+        ctx.accounts.task_history.initialize(task.key(), ctx.bumps.task_history);
 
-        task.task_id = task_id.clone();
-        task.campaign = ctx.accounts.campaign.key();
-        task.creator = ctx.accounts.creator.key();
-        task.recipient = recipient;
-        task.title = title.clone();
-        task.deliverables = deliverables;
-        task.target_budget = target_budget;
-        task.deadline = deadline;
-        task.state = TaskState::Draft;
-        task.created_at = clock.unix_timestamp;
-        task.updated_at = clock.unix_timestamp;
-        task.bump = ctx.bumps.task;
+        add_task_to_campaign(
+            &ctx.accounts.campaign_registry_program,
+            &ctx.accounts.campaign,
+            task,
+        )?;
+
+        emit!(TaskCreated {
+            task_pubkey: task.key(),
+            task_id: task.task_id.clone(),
+            campaign: ctx.accounts.campaign.key(),
+            creator: ctx.accounts.creator.key(),
+            title: task.title.clone(),
+            target_budget: task.target_budget,
+            approval_model: task.approval_model.clone(),
+            kind: task.kind.clone(),
+            template: None,
+            created_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create a competitive bounty: registered participants submit entries
+    /// until the deadline, then the selector picks winners and prize shares
+    /// (campaign creator only, as with create_task)
+    pub fn create_bounty(
+        ctx: Context<CreateTask>,
+        task_id: String,
+        params: CreateBountyParams,
+    ) -> Result<()> {
+        let params = params.into_new_task(task_id);
+        params.validate()?;
+
+        let task = &mut ctx.accounts.task;
+        let clock = Clock::get()?;
+        require!(
+            params.deadline.is_some_and(|deadline| deadline > clock.unix_timestamp),
+            TaskError::InvalidDeadline
+        );
+
+        task.initialize(
+            params,
+            ctx.accounts.campaign.key(),
+            ctx.accounts.creator.key(),
+            clock.unix_timestamp,
+            ctx.bumps.task,
+        );
+        ctx.accounts.task_history.initialize(task.key(), ctx.bumps.task_history);
+
+        add_task_to_campaign(
+            &ctx.accounts.campaign_registry_program,
+            &ctx.accounts.campaign,
+            task,
+        )?;
 
         emit!(TaskCreated {
             task_pubkey: task.key(),
+            task_id: task.task_id.clone(),
+            campaign: ctx.accounts.campaign.key(),
+            creator: ctx.accounts.creator.key(),
+            title: task.title.clone(),
+            target_budget: task.target_budget,
+            approval_model: task.approval_model.clone(),
+            kind: task.kind.clone(),
+            template: None,
+            created_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create a reusable task template
+    pub fn create_template(
+        ctx: Context<CreateTemplate>,
+        template_id: String,
+        params: CreateTemplateParams,
+    ) -> Result<()> {
+        require!(Task::validate_task_id(&template_id), TaskError::InvalidTemplateId);
+        params.validate()?;
+
+        let template = &mut ctx.accounts.template;
+        let clock = Clock::get()?;
+        let review_window_seconds = params.review_window_seconds();
+
+        template.template_id = template_id.clone();
+        template.creator = ctx.accounts.creator.key();
+        template.title_pattern = params.title_pattern.clone();
+        template.deliverables = params.deliverables;
+        template.default_deadline_offset = params.default_deadline_offset;
+        template.approval_model = params.approval_model;
+        template.veto_threshold_bps = params.veto_threshold_bps;
+        template.max_revisions = params.max_revisions;
+        template.review_window_seconds = review_window_seconds;
+        template.reviewers = params.reviewers;
+        template.milestones = params.milestones;
+        template.tasks_created = 0;
+        template.created_at = clock.unix_timestamp;
+        template.bump = ctx.bumps.template;
+
+        emit!(TaskTemplateCreated {
+            template_pubkey: template.key(),
+            template_id,
+            creator: ctx.accounts.creator.key(),
+            title_pattern: params.title_pattern,
+            created_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create a task from a template, filling the title placeholder and
+    /// applying any overrides to the template defaults (campaign creator only,
+    /// as with create_task)
+    pub fn create_task_from_template(
+        ctx: Context<CreateTaskFromTemplate>,
+        task_id: String,
+        title_param: String,
+        target_budget: u64,
+        recipient: Option<Pubkey>,
+        overrides: TemplateOverrides,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        let params = ctx.accounts.template.instantiate(
             task_id,
+            &title_param,
+            target_budget,
+            recipient,
+            overrides,
+            clock.unix_timestamp,
+        )?;
+        params.validate()?;
+
+        let task = &mut ctx.accounts.task;
+        task.initialize(
+            params,
+            ctx.accounts.campaign.key(),
+            ctx.accounts.creator.key(),
+            clock.unix_timestamp,
+            ctx.bumps.task,
+        );
+        ctx.accounts.task_history.initialize(task.key(), ctx.bumps.task_history);
+
+        add_task_to_campaign(
+            &ctx.accounts.campaign_registry_program,
+            &ctx.accounts.campaign,
+            task,
+        )?;
+
+        let template = &mut ctx.accounts.template;
+        template.tasks_created = template.tasks_created.checked_add(1).unwrap();
+
+        emit!(TaskCreated {
+            task_pubkey: task.key(),
+            task_id: task.task_id.clone(),
             campaign: ctx.accounts.campaign.key(),
             creator: ctx.accounts.creator.key(),
-            title,
+            title: task.title.clone(),
             target_budget,
+            approval_model: task.approval_model.clone(),
+            kind: task.kind.clone(),
+            template: Some(template.key()),
             created_at: clock.unix_timestamp,
         });
 
@@ -58,20 +246,65 @@ pub mod task_manager {
     }
 
     /// Open budget voting
-    pub fn start_budget_voting(ctx: Context<UpdateTaskState>) -> Result<()> {
+    /// The creator sets how long the vote stays open (default 7 days),
+    /// whether votes are open or commit-reveal, how votes are aggregated, and
+    /// the range votes must fall in (default 25%-400% of target)
+    pub fn start_budget_voting(
+        ctx: Context<UpdateTaskState>,
+        voting_duration: Option<i64>,
+        ballot: BudgetBallot,
+        aggregation: BudgetAggregation,
+        bounds: Option<BudgetBounds>,
+    ) -> Result<()> {
         let task = &mut ctx.accounts.task;
         let clock = Clock::get()?;
 
+        require_keys_eq!(
+            ctx.accounts.authority.key(),
+            task.creator,
+            TaskError::UnauthorizedCreator
+        );
         require!(
             task.can_transition_to(&TaskState::VotingBudget),
             TaskError::InvalidStateTransition
         );
 
+        let voting_duration = voting_duration.unwrap_or(DEFAULT_BUDGET_VOTING_SECONDS);
+        require!(
+            (MIN_BUDGET_VOTING_SECONDS..=MAX_BUDGET_VOTING_SECONDS).contains(&voting_duration),
+            TaskError::InvalidBudgetVotingDuration
+        );
+        require!(
+            Task::validate_budget_ballot(&ballot),
+            TaskError::InvalidBudgetBallot
+        );
+        require!(
+            Task::validate_budget_aggregation(&aggregation),
+            TaskError::InvalidBudgetAggregation
+        );
+        let bounds = bounds.unwrap_or_default();
+        require!(
+            Task::validate_budget_bounds(&bounds),
+            TaskError::InvalidBudgetBounds
+        );
+
         let old_state = task.state.clone();
         task.state = TaskState::VotingBudget;
         task.budget_voting_started_at = Some(clock.unix_timestamp);
+        task.budget_voting_duration = voting_duration;
+        task.budget_ballot = ballot.clone();
+        task.budget_aggregation = aggregation.clone();
+        task.budget_bounds = bounds.clone();
         task.updated_at = clock.unix_timestamp;
 
+        ctx.accounts.task_history.record(
+            old_state.clone(),
+            task.state.clone(),
+            ctx.accounts.authority.key(),
+            &clock,
+            [0; 32],
+        );
+
         emit!(TaskStateChanged {
             task_pubkey: task.key(),
             task_id: task.task_id.clone(),
@@ -84,6 +317,10 @@ pub mod task_manager {
             task_pubkey: task.key(),
             task_id: task.task_id.clone(),
             started_at: clock.unix_timestamp,
+            ends_at: clock.unix_timestamp + voting_duration,
+            ballot,
+            aggregation,
+            bounds,
         });
 
         Ok(())
@@ -98,12 +335,26 @@ pub mod task_manager {
             task.can_transition_to(&TaskState::BudgetFinalized),
             TaskError::InvalidStateTransition
         );
+        require_keys_eq!(
+            ctx.accounts.budget_aggregate.key(),
+            Task::budget_finalization_authority(&task.key()),
+            TaskError::UnauthorizedBudgetFinalization
+        );
 
+        let old_state = task.state.clone();
         task.finalized_budget = Some(finalized_budget);
         task.state = TaskState::BudgetFinalized;
         task.budget_finalized_at = Some(clock.unix_timestamp);
         task.updated_at = clock.unix_timestamp;
 
+        ctx.accounts.task_history.record(
+            old_state,
+            task.state.clone(),
+            ctx.accounts.budget_aggregate.key(),
+            &clock,
+            [0; 32],
+        );
+
         emit!(BudgetFinalized {
             task_pubkey: task.key(),
             task_id: task.task_id.clone(),
@@ -114,7 +365,117 @@ pub mod task_manager {
         Ok(())
     }
 
+    /// Set the recipient payout split table (before funding opens)
+    pub fn set_recipient_splits(
+        ctx: Context<SetRecipientSplits>,
+        splits: Vec<RecipientSplit>,
+    ) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        require!(task.can_set_recipient_splits(), TaskError::RecipientSplitsLocked);
+        require!(
+            Task::validate_recipient_splits(&splits),
+            TaskError::InvalidRecipientSplits
+        );
+
+        // The submitting recipient must be one of the split recipients;
+        // if none is assigned yet, the first entry leads the delivery
+        match task.recipient {
+            Some(recipient) => require!(
+                splits.iter().any(|s| s.recipient == recipient),
+                TaskError::InvalidRecipientSplits
+            ),
+            None => task.recipient = Some(splits[0].recipient),
+        }
+
+        task.recipient_splits = splits.clone();
+        task.updated_at = clock.unix_timestamp;
+
+        emit!(RecipientSplitsSet {
+            task_pubkey: task.key(),
+            task_id: task.task_id.clone(),
+            splits,
+            set_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Extend the deadline after a passing donor vote (called by approval_vote via CPI)
+    pub fn extend_deadline(ctx: Context<ExtendDeadline>, new_deadline: i64) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        require_keys_eq!(
+            ctx.accounts.authority.key(),
+            Task::deadline_extension_authority(&task.key()),
+            TaskError::UnauthorizedApprover
+        );
+        require!(
+            task.can_extend_deadline(clock.unix_timestamp),
+            TaskError::DeadlineNotExtendable
+        );
+
+        let old_deadline = task.deadline.unwrap();
+        require!(new_deadline > old_deadline, TaskError::InvalidDeadline);
+
+        task.deadline = Some(new_deadline);
+        task.updated_at = clock.unix_timestamp;
+
+        emit!(TaskDeadlineExtended {
+            task_pubkey: task.key(),
+            task_id: task.task_id.clone(),
+            old_deadline,
+            new_deadline,
+            extended_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Record that an approved task has been paid (called by task_escrow via
+    /// CPI, signed by the escrow PDA, once the payout transfers succeed)
+    pub fn mark_paid_out(ctx: Context<MarkPaidOut>) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        require_keys_eq!(
+            ctx.accounts.escrow.key(),
+            Task::payout_authority(&task.key()),
+            TaskError::UnauthorizedPayout
+        );
+        require!(
+            task.can_transition_to(&TaskState::PaidOut),
+            TaskError::InvalidStateTransition
+        );
+
+        let old_state = task.state.clone();
+        task.state = TaskState::PaidOut;
+        task.updated_at = clock.unix_timestamp;
+
+        ctx.accounts.task_history.record(
+            old_state.clone(),
+            task.state.clone(),
+            ctx.accounts.escrow.key(),
+            &clock,
+            [0; 32],
+        );
+
+        emit!(TaskStateChanged {
+            task_pubkey: task.key(),
+            task_id: task.task_id.clone(),
+            old_state,
+            new_state: task.state.clone(),
+            changed_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
     /// Submit proof of work
+    /// Only callable via proof_registry, which owns the Proof account and
+    /// signs with its PDA; the task just records the transition.
     pub fn submit_proof(
         ctx: Context<SubmitProof>,
         proof_hash: String,
@@ -128,13 +489,26 @@ pub mod task_manager {
             TaskError::InvalidStateTransition
         );
         require!(task.recipient.is_some(), TaskError::RecipientNotSet);
+        require_keys_eq!(
+            ctx.accounts.proof.key(),
+            Task::proof_authority(&task.key()),
+            TaskError::UnauthorizedProofSubmission
+        );
 
-        task.proof_hash = Some(proof_hash.clone());
-        task.proof_uri = Some(proof_uri.clone());
+        let old_state = task.state.clone();
         task.proof_submitted_at = Some(clock.unix_timestamp);
+        task.veto_window_ends_at = None;
         task.state = TaskState::SubmittedForReview;
         task.updated_at = clock.unix_timestamp;
 
+        ctx.accounts.task_history.record(
+            old_state,
+            task.state.clone(),
+            ctx.accounts.recipient.key(),
+            &clock,
+            [0; 32],
+        );
+
         emit!(ProofSubmitted {
             task_pubkey: task.key(),
             task_id: task.task_id.clone(),
@@ -147,20 +521,52 @@ pub mod task_manager {
         Ok(())
     }
 
-    /// Approve task (called after donor vote)
+    /// Approve task
+    /// Must be signed by the approval authority for the task's approval model.
+    /// Hybrid: the reviewer's approval opens a donor veto window instead.
     pub fn approve_task(ctx: Context<UpdateTaskState>) -> Result<()> {
         let task = &mut ctx.accounts.task;
         let clock = Clock::get()?;
 
+        require_keys_eq!(
+            ctx.accounts.authority.key(),
+            task.approval_authority(&task.key()),
+            TaskError::UnauthorizedApprover
+        );
+        require!(task.veto_window_ends_at.is_none(), TaskError::VetoWindowOpen);
         require!(
             task.can_transition_to(&TaskState::Approved),
             TaskError::InvalidStateTransition
         );
 
+        if task.approval_model == ApprovalModel::Hybrid && task.state == TaskState::SubmittedForReview {
+            let ends_at = clock.unix_timestamp + VETO_WINDOW_SECONDS;
+            task.veto_window_ends_at = Some(ends_at);
+            task.updated_at = clock.unix_timestamp;
+
+            emit!(VetoWindowOpened {
+                task_pubkey: task.key(),
+                task_id: task.task_id.clone(),
+                veto_threshold_bps: task.veto_threshold_bps,
+                ends_at,
+            });
+
+            return Ok(());
+        }
+
+        let old_state = task.state.clone();
         task.state = TaskState::Approved;
         task.approved_at = Some(clock.unix_timestamp);
         task.updated_at = clock.unix_timestamp;
 
+        ctx.accounts.task_history.record(
+            old_state,
+            task.state.clone(),
+            ctx.accounts.authority.key(),
+            &clock,
+            [0; 32],
+        );
+
         emit!(TaskApproved {
             task_pubkey: task.key(),
             task_id: task.task_id.clone(),
@@ -170,75 +576,940 @@ pub mod task_manager {
         Ok(())
     }
 
-    /// Reject task
-    pub fn reject_task(ctx: Context<UpdateTaskState>) -> Result<()> {
+    /// Approve a submission nobody acted on (permissionless)
+    /// Once the review window and finalization grace period lapse with no
+    /// rejection or open dispute (checked against dispute_module's
+    /// ["dispute", task] account), the task is approved; a hybrid task opens its
+    /// donor veto window as if the reviewer had approved. Only task parties can
+    /// open disputes, and one left unresolved past its resolution deadline no
+    /// longer blocks finalization.
+    pub fn finalize_review(ctx: Context<FinalizeReview>) -> Result<()> {
         let task = &mut ctx.accounts.task;
         let clock = Clock::get()?;
 
         require!(
-            task.can_transition_to(&TaskState::Rejected),
-            TaskError::InvalidStateTransition
+            task.can_finalize_review(clock.unix_timestamp),
+            TaskError::ReviewWindowOpen
+        );
+        require!(
+            !dispute_is_open(&ctx.accounts.dispute, clock.unix_timestamp)?,
+            TaskError::DisputeOpen
         );
 
-        task.state = TaskState::Rejected;
+        emit!(ReviewWindowLapsed {
+            task_pubkey: task.key(),
+            task_id: task.task_id.clone(),
+            review_window_ended_at: task.review_window_ends_at().unwrap(),
+            finalized_at: clock.unix_timestamp,
+        });
+
+        if task.approval_model == ApprovalModel::Hybrid {
+            let ends_at = clock.unix_timestamp + VETO_WINDOW_SECONDS;
+            task.veto_window_ends_at = Some(ends_at);
+            task.updated_at = clock.unix_timestamp;
+
+            emit!(VetoWindowOpened {
+                task_pubkey: task.key(),
+                task_id: task.task_id.clone(),
+                veto_threshold_bps: task.veto_threshold_bps,
+                ends_at,
+            });
+
+            return Ok(());
+        }
+
+        let old_state = task.state.clone();
+        task.state = TaskState::Approved;
+        task.approved_at = Some(clock.unix_timestamp);
         task.updated_at = clock.unix_timestamp;
 
-        emit!(TaskRejected {
+        ctx.accounts.task_history.record(
+            old_state,
+            task.state.clone(),
+            ctx.accounts.authority.key(),
+            &clock,
+            [0; 32],
+        );
+
+        emit!(TaskApproved {
             task_pubkey: task.key(),
             task_id: task.task_id.clone(),
-            rejected_at: clock.unix_timestamp,
+            approved_at: clock.unix_timestamp,
         });
 
         Ok(())
     }
-}
-
-// Account contexts
-#[derive(Accounts)]
-#[instruction(task_id: String)]
-pub struct CreateTask<'info> {
-    #[account(
-        init,
-        payer = creator,
-        space = Task::LEN,
-        seeds = [b"task", campaign.key().as_ref(), task_id.as_bytes()],
-        bump
-    )]
-    pub task: Account<'info, Task>,
 
-    /// CHECK: Validated by campaign_registry
-    pub campaign: UncheckedAccount<'info>,
+    /// Approve a hybrid task once its veto window passes without a veto (permissionless)
+    pub fn close_veto_window(ctx: Context<CloseVetoWindow>) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        let clock = Clock::get()?;
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
+        require!(
+            task.approval_model == ApprovalModel::Hybrid,
+            TaskError::NotHybridApproval
+        );
+        require!(task.veto_window_ends_at.is_some(), TaskError::VetoWindowClosed);
+        require!(
+            !task.is_veto_window_open(clock.unix_timestamp),
+            TaskError::VetoWindowOpen
+        );
+        require!(
+            task.can_transition_to(&TaskState::Approved),
+            TaskError::InvalidStateTransition
+        );
 
-    pub system_program: Program<'info, System>,
-}
+        let old_state = task.state.clone();
+        task.state = TaskState::Approved;
+        task.veto_window_ends_at = None;
+        task.approved_at = Some(clock.unix_timestamp);
+        task.updated_at = clock.unix_timestamp;
 
-#[derive(Accounts)]
-pub struct UpdateTaskState<'info> {
-    #[account(mut)]
-    pub task: Account<'info, Task>,
+        ctx.accounts.task_history.record(
+            old_state,
+            task.state.clone(),
+            ctx.accounts.authority.key(),
+            &clock,
+            [0; 32],
+        );
 
-    pub authority: Signer<'info>,
-}
+        emit!(TaskApproved {
+            task_pubkey: task.key(),
+            task_id: task.task_id.clone(),
+            approved_at: clock.unix_timestamp,
+        });
 
-#[derive(Accounts)]
-pub struct FinalizeBudget<'info> {
-    #[account(mut)]
-    pub task: Account<'info, Task>,
+        Ok(())
+    }
 
-    /// CHECK: Only budget_vote program can call this
-    pub budget_vote_program: UncheckedAccount<'info>,
-}
+    /// Donor veto of a hybrid reviewer approval (called by approval_vote via CPI)
+    pub fn veto_approval(ctx: Context<UpdateTaskState>) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        let clock = Clock::get()?;
 
-#[derive(Accounts)]
-pub struct SubmitProof<'info> {
-    #[account(
-        mut,
+        require_keys_eq!(
+            ctx.accounts.authority.key(),
+            Task::veto_authority(&task.key()),
+            TaskError::UnauthorizedApprover
+        );
+        require!(
+            task.is_veto_window_open(clock.unix_timestamp),
+            TaskError::VetoWindowClosed
+        );
+        require!(
+            task.can_transition_to(&TaskState::Disputed),
+            TaskError::InvalidStateTransition
+        );
+
+        let old_state = task.state.clone();
+        task.state = TaskState::Disputed;
+        task.veto_window_ends_at = None;
+        task.updated_at = clock.unix_timestamp;
+
+        ctx.accounts.task_history.record(
+            old_state.clone(),
+            task.state.clone(),
+            ctx.accounts.authority.key(),
+            &clock,
+            [0; 32],
+        );
+
+        emit!(TaskStateChanged {
+            task_pubkey: task.key(),
+            task_id: task.task_id.clone(),
+            old_state,
+            new_state: task.state.clone(),
+            changed_at: clock.unix_timestamp,
+        });
+
+        emit!(TaskVetoed {
+            task_pubkey: task.key(),
+            task_id: task.task_id.clone(),
+            vetoed_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Hold submitted work for a dispute (called by dispute_module via CPI)
+    /// The dispute then decides the task through approve_task or reject_task
+    pub fn dispute_task(ctx: Context<UpdateTaskState>) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        require_keys_eq!(
+            ctx.accounts.authority.key(),
+            Task::dispute_authority(&task.key()),
+            TaskError::UnauthorizedApprover
+        );
+        require!(
+            task.state == TaskState::SubmittedForReview,
+            TaskError::InvalidStateTransition
+        );
+
+        let old_state = task.state.clone();
+        task.state = TaskState::Disputed;
+        task.veto_window_ends_at = None;
+        task.updated_at = clock.unix_timestamp;
+
+        ctx.accounts.task_history.record(
+            old_state.clone(),
+            task.state.clone(),
+            ctx.accounts.authority.key(),
+            &clock,
+            [0; 32],
+        );
+
+        emit!(TaskStateChanged {
+            task_pubkey: task.key(),
+            task_id: task.task_id.clone(),
+            old_state,
+            new_state: task.state.clone(),
+            changed_at: clock.unix_timestamp,
+        });
+
+        emit!(TaskDisputed {
+            task_pubkey: task.key(),
+            task_id: task.task_id.clone(),
+            disputed_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Reject submitted work with reviewer feedback
+    /// Until the revision limit or deadline is reached, the task goes back to
+    /// InProgress for the recipient to resubmit; after that rejection is final
+    pub fn reject_task(ctx: Context<UpdateTaskState>, feedback_hash: [u8; 32]) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        require_keys_eq!(
+            ctx.accounts.authority.key(),
+            task.approval_authority(&task.key()),
+            TaskError::UnauthorizedApprover
+        );
+        require!(task.veto_window_ends_at.is_none(), TaskError::VetoWindowOpen);
+
+        task.last_feedback_hash = Some(feedback_hash);
+        task.updated_at = clock.unix_timestamp;
+
+        if task.can_request_changes(clock.unix_timestamp) {
+            let old_state = task.state.clone();
+            task.state = TaskState::InProgress;
+            task.revision_count += 1;
+
+            ctx.accounts.task_history.record(
+                old_state.clone(),
+                task.state.clone(),
+                ctx.accounts.authority.key(),
+                &clock,
+                feedback_hash,
+            );
+
+            emit!(TaskStateChanged {
+                task_pubkey: task.key(),
+                task_id: task.task_id.clone(),
+                old_state,
+                new_state: task.state.clone(),
+                changed_at: clock.unix_timestamp,
+            });
+
+            emit!(ChangesRequested {
+                task_pubkey: task.key(),
+                task_id: task.task_id.clone(),
+                feedback_hash,
+                revision_count: task.revision_count,
+                max_revisions: task.max_revisions,
+                requested_at: clock.unix_timestamp,
+            });
+
+            return Ok(());
+        }
+
+        require!(
+            task.can_transition_to(&TaskState::Rejected),
+            TaskError::InvalidStateTransition
+        );
+
+        let old_state = task.state.clone();
+        task.state = TaskState::Rejected;
+
+        ctx.accounts.task_history.record(
+            old_state,
+            task.state.clone(),
+            ctx.accounts.authority.key(),
+            &clock,
+            feedback_hash,
+        );
+
+        emit!(TaskRejected {
+            task_pubkey: task.key(),
+            task_id: task.task_id.clone(),
+            feedback_hash,
+            rejected_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Register as a participant in a bounty
+    pub fn register_for_bounty(ctx: Context<RegisterForBounty>) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        require!(
+            task.can_accept_bounty_entries(clock.unix_timestamp),
+            TaskError::BountyEntriesClosed
+        );
+
+        ctx.accounts.entry.initialize(
+            task.key(),
+            ctx.accounts.participant.key(),
+            clock.unix_timestamp,
+            ctx.bumps.entry,
+        );
+
+        emit!(BountyParticipantRegistered {
+            task_pubkey: task.key(),
+            task_id: task.task_id.clone(),
+            participant: ctx.accounts.participant.key(),
+            registered_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Submit (or replace) a bounty entry before the deadline
+    /// Called by proof_registry via CPI, signed by the entry's proof PDA;
+    /// the entry keeps a reference to that Proof account
+    pub fn submit_bounty_entry(
+        ctx: Context<SubmitBountyEntry>,
+        proof_hash: String,
+        proof_uri: String,
+    ) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        require!(
+            task.can_accept_bounty_entries(clock.unix_timestamp),
+            TaskError::BountyEntriesClosed
+        );
+        require_keys_eq!(
+            ctx.accounts.proof.key(),
+            Task::entry_proof_authority(&task.key(), &ctx.accounts.participant.key()),
+            TaskError::UnauthorizedProofSubmission
+        );
+
+        ctx.accounts.entry.submit(ctx.accounts.proof.key(), clock.unix_timestamp);
+
+        emit!(BountyEntrySubmitted {
+            task_pubkey: task.key(),
+            task_id: task.task_id.clone(),
+            participant: ctx.accounts.participant.key(),
+            proof_hash,
+            proof_uri,
+            submitted_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Pick a bounty's winners and prize shares after the entry deadline
+    /// Winners become the task's recipient splits, so escrow pays them out
+    /// through the split payout. remaining_accounts: each winner's
+    /// BountyEntry, in the same order as `winners`.
+    pub fn select_bounty_winners<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateTaskState<'info>>,
+        winners: Vec<RecipientSplit>,
+    ) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        let clock = Clock::get()?;
+        let task_key = task.key();
+
+        require!(
+            task.is_winner_selector(&task_key, &ctx.accounts.authority.key()),
+            TaskError::UnauthorizedWinnerSelector
+        );
+        require!(
+            task.can_select_winners(clock.unix_timestamp),
+            TaskError::WinnerSelectionNotOpen
+        );
+
+        let entries = ctx
+            .remaining_accounts
+            .iter()
+            .map(|info| Account::<BountyEntry>::try_from(info).map(|entry| entry.into_inner()))
+            .collect::<Result<Vec<_>>>()?;
+        require!(
+            BountyEntry::validate_winners(&task_key, &winners, &entries),
+            TaskError::InvalidBountyWinners
+        );
+
+        require!(
+            task.can_transition_to(&TaskState::Approved),
+            TaskError::InvalidStateTransition
+        );
+
+        let old_state = task.state.clone();
+        task.recipient = Some(winners[0].recipient);
+        task.recipient_splits = winners.clone();
+        task.state = TaskState::Approved;
+        task.approved_at = Some(clock.unix_timestamp);
+        task.updated_at = clock.unix_timestamp;
+
+        ctx.accounts.task_history.record(
+            old_state,
+            task.state.clone(),
+            ctx.accounts.authority.key(),
+            &clock,
+            [0; 32],
+        );
+
+        emit!(BountyWinnersSelected {
+            task_pubkey: task_key,
+            task_id: task.task_id.clone(),
+            winners,
+            selected_by: ctx.accounts.authority.key(),
+            selected_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set up the skill tag registry (DAO authority only)
+    pub fn initialize_tag_registry(ctx: Context<InitializeTagRegistry>) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        registry.authority = ctx.accounts.authority.key();
+        registry.tag_count = 0;
+        registry.bump = ctx.bumps.registry;
+
+        Ok(())
+    }
+
+    /// Add a skill tag and its empty task index (registry authority only)
+    pub fn create_skill_tag(ctx: Context<CreateSkillTag>, name: String) -> Result<()> {
+        require!(SkillTag::validate_name(&name), TaskError::InvalidSkillTagName);
+
+        let clock = Clock::get()?;
+
+        let skill_tag = &mut ctx.accounts.skill_tag;
+        skill_tag.name = name.clone();
+        skill_tag.active = true;
+        skill_tag.created_at = clock.unix_timestamp;
+        skill_tag.bump = ctx.bumps.skill_tag;
+
+        let tag_index = &mut ctx.accounts.tag_index;
+        tag_index.tag = name.clone();
+        tag_index.tasks = Vec::new();
+        tag_index.bump = ctx.bumps.tag_index;
+
+        let registry = &mut ctx.accounts.registry;
+        registry.tag_count = registry.tag_count.checked_add(1).unwrap();
+
+        emit!(SkillTagCreated {
+            tag: skill_tag.key(),
+            name,
+            created_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Retire or reinstate a skill tag (registry authority only)
+    pub fn set_skill_tag_active(ctx: Context<SetSkillTagActive>, active: bool) -> Result<()> {
+        let skill_tag = &mut ctx.accounts.skill_tag;
+        skill_tag.active = active;
+
+        emit!(SkillTagUpdated {
+            tag: skill_tag.key(),
+            name: skill_tag.name.clone(),
+            active,
+            updated_at: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set a task's skill tags (creator only, once)
+    /// While the task needs a recipient it is listed in each tag's index;
+    /// tags set earlier are listed with list_tagged_task once funding opens.
+    /// remaining_accounts: [skill_tag, tag_index (mut)] per tag, in order.
+    pub fn set_skill_tags<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetSkillTags<'info>>,
+        tags: Vec<String>,
+    ) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        require!(task.skill_tags.is_empty(), TaskError::SkillTagsAlreadySet);
+        require!(SkillTag::validate_tags(&tags), TaskError::InvalidSkillTags);
+        require!(
+            ctx.remaining_accounts.len() == tags.len() * 2,
+            TaskError::InvalidTagAccounts
+        );
+
+        let task_key = task.key();
+        let listed = task.needs_recipient();
+        for (tag, accounts) in tags.iter().zip(ctx.remaining_accounts.chunks(2)) {
+            let skill_tag = Account::<SkillTag>::try_from(&accounts[0])?;
+            require!(skill_tag.name == *tag, TaskError::InvalidTagAccounts);
+            require!(skill_tag.active, TaskError::SkillTagInactive);
+
+            let mut tag_index = Account::<TagIndex>::try_from(&accounts[1])?;
+            require!(tag_index.tag == *tag, TaskError::InvalidTagAccounts);
+            if listed {
+                require!(tag_index.insert(task_key), TaskError::TagIndexFull);
+                tag_index.exit(&crate::ID)?;
+            }
+        }
+
+        task.skill_tags = tags.clone();
+        task.updated_at = clock.unix_timestamp;
+
+        emit!(SkillTagsSet {
+            task_pubkey: task_key,
+            task_id: task.task_id.clone(),
+            tags,
+            listed,
+            set_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// List a tagged task in a tag index once it needs a recipient
+    /// Permissionless, for tasks tagged before funding opened
+    pub fn list_tagged_task(ctx: Context<ListTaggedTask>) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let tag_index = &mut ctx.accounts.tag_index;
+
+        require!(task.needs_recipient(), TaskError::TaskNotOpen);
+        require!(task.skill_tags.contains(&tag_index.tag), TaskError::TaskNotTagged);
+        require!(tag_index.insert(task.key()), TaskError::TagIndexFull);
+
+        Ok(())
+    }
+
+    /// Drop a task that no longer needs a recipient from a tag index
+    /// Permissionless, so indexes stay current without the creator
+    pub fn prune_tag_index(ctx: Context<PruneTagIndex>) -> Result<()> {
+        let task = &ctx.accounts.task;
+
+        require!(!task.needs_recipient(), TaskError::TaskStillOpen);
+        require!(
+            ctx.accounts.tag_index.remove(&task.key()),
+            TaskError::TaskNotInTagIndex
+        );
+
+        Ok(())
+    }
+}
+
+/// Whether the task's ["dispute", task] account holds a dispute that is still open
+/// (an uninitialized account means no dispute was ever opened)
+fn dispute_is_open(dispute: &AccountInfo, current_time: i64) -> Result<bool> {
+    if dispute.data_is_empty() {
+        return Ok(false);
+    }
+    require_keys_eq!(*dispute.owner, dispute_module_program::ID, TaskError::InvalidDispute);
+
+    let data = dispute.try_borrow_data()?;
+    let header = DisputeHeader::decode(&data).ok_or(TaskError::InvalidDispute)?;
+
+    Ok(header.is_open(current_time))
+}
+
+/// CPI campaign_registry::increment_task_count, signed by the new task PDA
+fn add_task_to_campaign<'info>(
+    campaign_registry_program: &Program<'info, CampaignRegistry>,
+    campaign: &Account<'info, Campaign>,
+    task: &Account<'info, Task>,
+) -> Result<()> {
+    let campaign_key = campaign.key();
+    let seeds = &[
+        b"task",
+        campaign_key.as_ref(),
+        task.task_id.as_bytes(),
+        &[task.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = IncrementTaskCount {
+        campaign: campaign.to_account_info(),
+        task: task.to_account_info(),
+    };
+    let cpi_program = campaign_registry_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    campaign_registry::cpi::increment_task_count(cpi_ctx, task.task_id.clone())
+}
+
+// Account contexts
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct CreateTask<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = Task::LEN,
+        seeds = [b"task", campaign.key().as_ref(), task_id.as_bytes()],
+        bump
+    )]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = TaskHistory::LEN,
+        seeds = [b"task_history", task.key().as_ref()],
+        bump
+    )]
+    pub task_history: Account<'info, TaskHistory>,
+
+    /// Campaign the task joins; only its creator may add tasks (campaigns
+    /// have no co-owners or members)
+    #[account(
+        mut,
+        constraint = campaign.can_add_tasks() @ TaskError::CampaignNotAcceptingTasks,
+        constraint = campaign.creator == creator.key() @ TaskError::NotCampaignCreator
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub campaign_registry_program: Program<'info, CampaignRegistry>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(template_id: String)]
+pub struct CreateTemplate<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = TaskTemplate::LEN,
+        seeds = [b"task_template", creator.key().as_ref(), template_id.as_bytes()],
+        bump
+    )]
+    pub template: Account<'info, TaskTemplate>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct CreateTaskFromTemplate<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = Task::LEN,
+        seeds = [b"task", campaign.key().as_ref(), task_id.as_bytes()],
+        bump
+    )]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = TaskHistory::LEN,
+        seeds = [b"task_history", task.key().as_ref()],
+        bump
+    )]
+    pub task_history: Account<'info, TaskHistory>,
+
+    #[account(
+        mut,
+        seeds = [b"task_template", template.creator.as_ref(), template.template_id.as_bytes()],
+        bump = template.bump
+    )]
+    pub template: Account<'info, TaskTemplate>,
+
+    /// Campaign the task joins; only its creator may add tasks (campaigns
+    /// have no co-owners or members)
+    #[account(
+        mut,
+        constraint = campaign.can_add_tasks() @ TaskError::CampaignNotAcceptingTasks,
+        constraint = campaign.creator == creator.key() @ TaskError::NotCampaignCreator
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub campaign_registry_program: Program<'info, CampaignRegistry>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTaskState<'info> {
+    #[account(mut)]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        mut,
+        seeds = [b"task_history", task.key().as_ref()],
+        bump = task_history.bump
+    )]
+    pub task_history: Account<'info, TaskHistory>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterForBounty<'info> {
+    #[account(
+        init,
+        payer = participant,
+        space = BountyEntry::LEN,
+        seeds = [b"bounty_entry", task.key().as_ref(), participant.key().as_ref()],
+        bump
+    )]
+    pub entry: Account<'info, BountyEntry>,
+
+    pub task: Account<'info, Task>,
+
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitBountyEntry<'info> {
+    #[account(
+        mut,
+        seeds = [b"bounty_entry", task.key().as_ref(), participant.key().as_ref()],
+        bump = entry.bump
+    )]
+    pub entry: Account<'info, BountyEntry>,
+
+    pub task: Account<'info, Task>,
+
+    /// Entry proof account in proof_registry, signing as the CPI caller
+    pub proof: Signer<'info>,
+
+    pub participant: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTagRegistry<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = TagRegistry::LEN,
+        seeds = [b"tag_registry"],
+        bump
+    )]
+    pub registry: Account<'info, TagRegistry>,
+
+    #[account(
+        mut,
+        address = dao_authority::ID @ TaskError::UnauthorizedTagAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct CreateSkillTag<'info> {
+    #[account(
+        mut,
+        seeds = [b"tag_registry"],
+        bump = registry.bump,
+        has_one = authority @ TaskError::UnauthorizedTagAuthority
+    )]
+    pub registry: Account<'info, TagRegistry>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = SkillTag::LEN,
+        seeds = [b"skill_tag", name.as_bytes()],
+        bump
+    )]
+    pub skill_tag: Account<'info, SkillTag>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TagIndex::LEN,
+        seeds = [b"tag_index", name.as_bytes()],
+        bump
+    )]
+    pub tag_index: Account<'info, TagIndex>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetSkillTagActive<'info> {
+    #[account(
+        seeds = [b"tag_registry"],
+        bump = registry.bump,
+        has_one = authority @ TaskError::UnauthorizedTagAuthority
+    )]
+    pub registry: Account<'info, TagRegistry>,
+
+    #[account(
+        mut,
+        seeds = [b"skill_tag", skill_tag.name.as_bytes()],
+        bump = skill_tag.bump
+    )]
+    pub skill_tag: Account<'info, SkillTag>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSkillTags<'info> {
+    #[account(
+        mut,
+        constraint = task.creator == creator.key() @ TaskError::UnauthorizedCreator
+    )]
+    pub task: Account<'info, Task>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ListTaggedTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"tag_index", tag_index.tag.as_bytes()],
+        bump = tag_index.bump
+    )]
+    pub tag_index: Account<'info, TagIndex>,
+
+    pub task: Account<'info, Task>,
+}
+
+#[derive(Accounts)]
+pub struct PruneTagIndex<'info> {
+    #[account(
+        mut,
+        seeds = [b"tag_index", tag_index.tag.as_bytes()],
+        bump = tag_index.bump
+    )]
+    pub tag_index: Account<'info, TagIndex>,
+
+    pub task: Account<'info, Task>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeReview<'info> {
+    #[account(mut)]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        mut,
+        seeds = [b"task_history", task.key().as_ref()],
+        bump = task_history.bump
+    )]
+    pub task_history: Account<'info, TaskHistory>,
+
+    /// CHECK: The task's dispute in dispute_module; must be unopened or settled
+    #[account(
+        seeds = [b"dispute", task.key().as_ref()],
+        bump,
+        seeds::program = dispute_module_program::ID
+    )]
+    pub dispute: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseVetoWindow<'info> {
+    #[account(mut)]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        mut,
+        seeds = [b"task_history", task.key().as_ref()],
+        bump = task_history.bump
+    )]
+    pub task_history: Account<'info, TaskHistory>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendDeadline<'info> {
+    #[account(mut)]
+    pub task: Account<'info, Task>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MarkPaidOut<'info> {
+    #[account(mut)]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        mut,
+        seeds = [b"task_history", task.key().as_ref()],
+        bump = task_history.bump
+    )]
+    pub task_history: Account<'info, TaskHistory>,
+
+    /// Escrow account in task_escrow, signing as the CPI caller
+    pub escrow: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRecipientSplits<'info> {
+    #[account(
+        mut,
+        constraint = task.creator == creator.key() @ TaskError::UnauthorizedCreator
+    )]
+    pub task: Account<'info, Task>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeBudget<'info> {
+    #[account(mut)]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        mut,
+        seeds = [b"task_history", task.key().as_ref()],
+        bump = task_history.bump
+    )]
+    pub task_history: Account<'info, TaskHistory>,
+
+    /// Budget aggregate in budget_vote, signing as the CPI caller
+    pub budget_aggregate: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitProof<'info> {
+    #[account(
+        mut,
         constraint = task.recipient == Some(recipient.key()) @ TaskError::UnauthorizedRecipient
     )]
     pub task: Account<'info, Task>,
 
+    #[account(
+        mut,
+        seeds = [b"task_history", task.key().as_ref()],
+        bump = task_history.bump
+    )]
+    pub task_history: Account<'info, TaskHistory>,
+
+    /// Proof account in proof_registry, signing as the CPI caller
+    pub proof: Signer<'info>,
+
     pub recipient: Signer<'info>,
 }
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// A participant's entry in a bounty
+/// PDA seeds: ["bounty_entry", task, participant]
+#[account]
+pub struct BountyEntry {
+    /// Bounty task this entry competes in
+    pub task: Pubkey,
+
+    /// Registered participant (paid directly if selected)
+    pub participant: Pubkey,
+
+    /// Entry's Proof account in proof_registry (the single record of the
+    /// submission), set on submission
+    pub proof: Option<Pubkey>,
+
+    pub registered_at: i64,
+
+    /// Latest submission; entries may be replaced until the deadline
+    pub submitted_at: Option<i64>,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl BountyEntry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // task
+        32 + // participant
+        1 + 32 + // proof
+        8 + // registered_at
+        1 + 8 + // submitted_at
+        1; // bump
+
+    pub fn initialize(&mut self, task: Pubkey, participant: Pubkey, now: i64, bump: u8) {
+        self.task = task;
+        self.participant = participant;
+        self.proof = None;
+        self.registered_at = now;
+        self.submitted_at = None;
+        self.bump = bump;
+    }
+
+    pub fn submit(&mut self, proof: Pubkey, now: i64) {
+        self.proof = Some(proof);
+        self.submitted_at = Some(now);
+    }
+
+    pub fn has_submitted(&self) -> bool {
+        self.submitted_at.is_some()
+    }
+
+    /// Validate a winner table against the entries passed alongside it:
+    /// a valid split table where every winner has a submitted entry in this
+    /// bounty, given in the same order as the winners
+    pub fn validate_winners(task: &Pubkey, winners: &[RecipientSplit], entries: &[BountyEntry]) -> bool {
+        Task::validate_recipient_splits(winners)
+            && winners.len() == entries.len()
+            && winners.iter().zip(entries).all(|(winner, entry)| {
+                entry.task == *task && entry.participant == winner.recipient && entry.has_submitted()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(task: Pubkey, participant: Pubkey, submitted: bool) -> BountyEntry {
+        let mut entry = BountyEntry {
+            task: Pubkey::default(),
+            participant: Pubkey::default(),
+            proof: None,
+            registered_at: 0,
+            submitted_at: None,
+            bump: 0,
+        };
+        entry.initialize(task, participant, 10, 255);
+        if submitted {
+            entry.submit(Task::entry_proof_authority(&task, &participant), 20);
+        }
+        entry
+    }
+
+    #[test]
+    fn test_submission_references_entry_proof() {
+        let (task, participant) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert!(!entry(task, participant, false).has_submitted());
+
+        let submitted = entry(task, participant, true);
+        assert!(submitted.has_submitted());
+        assert_eq!(submitted.proof, Some(Task::entry_proof_authority(&task, &participant)));
+        assert_ne!(submitted.proof, Some(Task::entry_proof_authority(&task, &Pubkey::new_unique())));
+    }
+
+    #[test]
+    fn test_validate_winners() {
+        let task = Pubkey::new_unique();
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let winners = vec![
+            RecipientSplit { recipient: a, share_bps: 7_000 },
+            RecipientSplit { recipient: b, share_bps: 3_000 },
+        ];
+
+        assert!(BountyEntry::validate_winners(&task, &winners, &[entry(task, a, true), entry(task, b, true)]));
+
+        // Entries out of order, missing, unsubmitted or from another bounty
+        assert!(!BountyEntry::validate_winners(&task, &winners, &[entry(task, b, true), entry(task, a, true)]));
+        assert!(!BountyEntry::validate_winners(&task, &winners, &[entry(task, a, true)]));
+        assert!(!BountyEntry::validate_winners(&task, &winners, &[entry(task, a, true), entry(task, b, false)]));
+        let other = Pubkey::new_unique();
+        assert!(!BountyEntry::validate_winners(&task, &winners, &[entry(task, a, true), entry(other, b, true)]));
+
+        // Shares must still sum to 100%
+        let short = vec![RecipientSplit { recipient: a, share_bps: 5_000 }];
+        assert!(!BountyEntry::validate_winners(&task, &short, &[entry(task, a, true)]));
+    }
+}
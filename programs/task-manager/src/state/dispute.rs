@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+/// Leading fields of dispute_module's Dispute account, up to its status
+/// dispute_module depends on this crate, so the layout is mirrored here and
+/// pinned by dispute_module's tests against its real Dispute account.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DisputeHeader {
+    pub task: Pubkey,
+    pub initiator: Pubkey,
+    pub reason: String,
+    pub opened_at: i64,
+    pub resolution_deadline: i64,
+    pub status: u8,
+}
+
+impl DisputeHeader {
+    /// DisputeStatus::Open is the first variant
+    pub const STATUS_OPEN: u8 = 0;
+
+    /// Anchor discriminator of dispute_module's Dispute account
+    pub fn discriminator() -> [u8; 8] {
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash(b"account:Dispute").to_bytes()[..8]);
+        discriminator
+    }
+
+    /// Decode from raw account data; None if it isn't a Dispute
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.get(..8)? != Self::discriminator() {
+            return None;
+        }
+        Self::deserialize(&mut &data[8..]).ok()
+    }
+
+    /// Still open and within its resolution window
+    /// A dispute nobody resolved by its deadline no longer holds up the task.
+    pub fn is_open(&self, current_time: i64) -> bool {
+        self.status == Self::STATUS_OPEN && current_time <= self.resolution_deadline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(header: &DisputeHeader, tail: &[u8]) -> Vec<u8> {
+        let mut data = DisputeHeader::discriminator().to_vec();
+        header.serialize(&mut data).unwrap();
+        data.extend_from_slice(tail);
+        data
+    }
+
+    #[test]
+    fn test_decode_and_expiry() {
+        let header = DisputeHeader {
+            task: Pubkey::new_unique(),
+            initiator: Pubkey::new_unique(),
+            reason: "work not delivered".to_string(),
+            opened_at: 100,
+            resolution_deadline: 200,
+            status: DisputeHeader::STATUS_OPEN,
+        };
+        // resolution and resolved_at follow the status
+        let data = encode(&header, &[0, 0]);
+
+        let decoded = DisputeHeader::decode(&data).unwrap();
+        assert_eq!(decoded, header);
+        assert!(decoded.is_open(200));
+        assert!(!decoded.is_open(201));
+
+        let resolved = DisputeHeader { status: 1, ..header };
+        assert!(!resolved.is_open(150));
+
+        let mut wrong_account = data.clone();
+        wrong_account[0] ^= 1;
+        assert!(DisputeHeader::decode(&wrong_account).is_none());
+        assert!(DisputeHeader::decode(&data[..20]).is_none());
+    }
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use crate::state::TaskState;
+
+/// Transitions kept on-chain per task; older entries are overwritten
+pub const HISTORY_CAPACITY: usize = 32;
+
+/// One task state transition
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct HistoryEntry {
+    pub old_state: TaskState,
+    pub new_state: TaskState,
+
+    /// Signer that caused the transition (program PDA for CPI callers)
+    pub actor: Pubkey,
+
+    pub slot: u64,
+    pub timestamp: i64,
+
+    /// Hash of the reason (reviewer feedback, rationale); zeroed if none
+    pub reason_hash: [u8; 32],
+}
+
+impl HistoryEntry {
+    pub const LEN: usize = 1 + 1 + 32 + 8 + 8 + 32;
+}
+
+/// Task state-transition history (ring buffer)
+/// PDA seeds: ["task_history", task]
+#[account]
+pub struct TaskHistory {
+    /// Task this history belongs to
+    pub task: Pubkey,
+
+    /// Transitions ever recorded; entry `n` lives at `n % HISTORY_CAPACITY`
+    pub total_transitions: u32,
+
+    /// Ring buffer of the latest transitions
+    pub entries: Vec<HistoryEntry>,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl TaskHistory {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // task
+        4 + // total_transitions
+        4 + HISTORY_CAPACITY * HistoryEntry::LEN + // entries
+        1; // bump
+
+    /// Start an empty history for a new task
+    pub fn initialize(&mut self, task: Pubkey, bump: u8) {
+        self.task = task;
+        self.total_transitions = 0;
+        self.entries = Vec::new();
+        self.bump = bump;
+    }
+
+    /// Append a transition, overwriting the oldest entry once full
+    pub fn record(
+        &mut self,
+        old_state: TaskState,
+        new_state: TaskState,
+        actor: Pubkey,
+        clock: &Clock,
+        reason_hash: [u8; 32],
+    ) {
+        let entry = HistoryEntry {
+            old_state,
+            new_state,
+            actor,
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+            reason_hash,
+        };
+
+        let index = self.total_transitions as usize % HISTORY_CAPACITY;
+        if index < self.entries.len() {
+            self.entries[index] = entry;
+        } else {
+            self.entries.push(entry);
+        }
+        self.total_transitions += 1;
+    }
+
+    /// Retained entries, oldest first
+    pub fn chronological(&self) -> Vec<&HistoryEntry> {
+        let split = if self.entries.len() < HISTORY_CAPACITY {
+            0
+        } else {
+            self.total_transitions as usize % HISTORY_CAPACITY
+        };
+        let (newer, older) = self.entries.split_at(split);
+        older.iter().chain(newer.iter()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(slot: u64) -> Clock {
+        Clock {
+            slot,
+            unix_timestamp: slot as i64,
+            ..Clock::default()
+        }
+    }
+
+    #[test]
+    fn test_history_ring_buffer() {
+        let mut history = TaskHistory {
+            task: Pubkey::default(),
+            total_transitions: 0,
+            entries: vec![],
+            bump: 0,
+        };
+
+        for slot in 0..(HISTORY_CAPACITY as u64 + 3) {
+            history.record(
+                TaskState::Draft,
+                TaskState::VotingBudget,
+                Pubkey::default(),
+                &clock(slot),
+                [0; 32],
+            );
+        }
+
+        assert_eq!(history.entries.len(), HISTORY_CAPACITY);
+        assert_eq!(history.total_transitions as usize, HISTORY_CAPACITY + 3);
+
+        // Oldest three were overwritten; order is preserved
+        let slots: Vec<u64> = history.chronological().iter().map(|e| e.slot).collect();
+        let expected: Vec<u64> = (3..(HISTORY_CAPACITY as u64 + 3)).collect();
+        assert_eq!(slots, expected);
+    }
+
+    #[test]
+    fn test_history_before_wrap() {
+        let mut history = TaskHistory {
+            task: Pubkey::default(),
+            total_transitions: 0,
+            entries: vec![],
+            bump: 0,
+        };
+
+        history.record(TaskState::Draft, TaskState::VotingBudget, Pubkey::default(), &clock(7), [0; 32]);
+        history.record(TaskState::VotingBudget, TaskState::BudgetFinalized, Pubkey::default(), &clock(9), [1; 32]);
+
+        let entries = history.chronological();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].slot, 7);
+        assert_eq!(entries[1].new_state, TaskState::BudgetFinalized);
+        assert_eq!(entries[1].reason_hash, [1; 32]);
+    }
+}
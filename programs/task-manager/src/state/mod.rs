@@ -1,3 +1,13 @@
 pub mod task;
+pub mod history;
+pub mod template;
+pub mod bounty;
+pub mod tag;
+pub mod dispute;
 
 pub use task::*;
+pub use history::*;
+pub use template::*;
+pub use bounty::*;
+pub use tag::*;
+pub use dispute::*;
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_SKILL_TAGS: usize = 3;
+pub const MAX_SKILL_TAG_LEN: usize = 32;
+
+/// Open tasks listed per tag; prune closed tasks to make room
+pub const MAX_TAG_INDEX_TASKS: usize = 64;
+
+/// Governed skill tag registry configuration
+/// PDA seeds: ["tag_registry"]
+#[account]
+pub struct TagRegistry {
+    pub authority: Pubkey,   // DAO multisig; only it can add or retire tags
+    pub tag_count: u32,
+    pub bump: u8,
+}
+
+impl TagRegistry {
+    pub const LEN: usize = 8 + 32 + 4 + 1;
+}
+
+/// A skill tag tasks may carry
+/// PDA seeds: ["skill_tag", name]
+#[account]
+pub struct SkillTag {
+    pub name: String,
+    pub active: bool,        // Retired tags stay on existing tasks but can't be added
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl SkillTag {
+    pub const LEN: usize = 8 + (4 + MAX_SKILL_TAG_LEN) + 1 + 8 + 1;
+
+    /// Tag names are 1..=MAX_SKILL_TAG_LEN lowercase ASCII letters, digits or '-'
+    pub fn validate_name(name: &str) -> bool {
+        !name.is_empty()
+            && name.len() <= MAX_SKILL_TAG_LEN
+            && name
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+    }
+
+    /// A task's tag set: 1..=MAX_SKILL_TAGS unique valid names
+    pub fn validate_tags(tags: &[String]) -> bool {
+        !tags.is_empty()
+            && tags.len() <= MAX_SKILL_TAGS
+            && tags.iter().enumerate().all(|(i, tag)| {
+                Self::validate_name(tag) && !tags[..i].contains(tag)
+            })
+    }
+}
+
+/// Open tasks carrying a tag that still need a recipient
+/// PDA seeds: ["tag_index", name]
+#[account]
+pub struct TagIndex {
+    pub tag: String,
+    pub tasks: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl TagIndex {
+    pub const LEN: usize = 8 + (4 + MAX_SKILL_TAG_LEN) + (4 + MAX_TAG_INDEX_TASKS * 32) + 1;
+
+    /// Add a task; false if the index is full
+    pub fn insert(&mut self, task: Pubkey) -> bool {
+        if self.tasks.contains(&task) {
+            return true;
+        }
+        if self.tasks.len() >= MAX_TAG_INDEX_TASKS {
+            return false;
+        }
+        self.tasks.push(task);
+        true
+    }
+
+    /// Remove a task; false if it wasn't listed
+    pub fn remove(&mut self, task: &Pubkey) -> bool {
+        match self.tasks.iter().position(|t| t == task) {
+            Some(i) => {
+                self.tasks.swap_remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_names() {
+        assert!(SkillTag::validate_name("rust"));
+        assert!(SkillTag::validate_name("smart-contracts"));
+        assert!(!SkillTag::validate_name(""));
+        assert!(!SkillTag::validate_name("Rust"));
+        assert!(!SkillTag::validate_name("ui design"));
+        assert!(!SkillTag::validate_name(&"a".repeat(MAX_SKILL_TAG_LEN + 1)));
+
+        let tags = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert!(SkillTag::validate_tags(&tags(&["rust", "solana"])));
+        assert!(!SkillTag::validate_tags(&tags(&[])));
+        assert!(!SkillTag::validate_tags(&tags(&["rust", "rust"])));
+        assert!(!SkillTag::validate_tags(&tags(&["a", "b", "c", "d"])));
+    }
+
+    #[test]
+    fn test_index_insert_and_remove() {
+        let mut index = TagIndex { tag: "rust".to_string(), tasks: vec![], bump: 0 };
+        let task = Pubkey::new_unique();
+
+        assert!(index.insert(task));
+        assert!(index.insert(task)); // already listed
+        assert_eq!(index.tasks.len(), 1);
+
+        for _ in 1..MAX_TAG_INDEX_TASKS {
+            assert!(index.insert(Pubkey::new_unique()));
+        }
+        assert!(!index.insert(Pubkey::new_unique()));
+
+        assert!(index.remove(&task));
+        assert!(!index.remove(&task));
+        assert_eq!(index.tasks.len(), MAX_TAG_INDEX_TASKS - 1);
+    }
+}
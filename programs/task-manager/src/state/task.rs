@@ -1,4 +1,10 @@
 use anchor_lang::prelude::*;
+use crate::error::TaskError;
+use crate::state::{MAX_SKILL_TAGS, MAX_SKILL_TAG_LEN};
+use crate::{
+    approval_vote_program, budget_vote_program, dispute_module_program, proof_registry_program,
+    reviewer_registry_program, task_escrow_program,
+};
 
 /// Task states - complete state machine
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
@@ -18,11 +24,139 @@ pub enum TaskState {
     Disputed,              // Under dispute
 }
 
+/// How submitted work gets approved, fixed at creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum ApprovalModel {
+    DonorVote,  // Contribution-weighted donor vote (approval_vote)
+    Reviewer,   // Staked reviewer decides (reviewer_registry)
+    Hybrid,     // Reviewer approves, donors may veto during a window
+}
+
+/// Who picks the winners of a bounty
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum WinnerSelection {
+    Creator,    // Task creator picks
+    Reviewer,   // Any reviewer in the task's reviewer pool picks
+    DonorVote,  // Contribution-weighted donor vote (approval_vote)
+}
+
+/// Standard tasks have a fixed recipient (or split); bounties accept
+/// entries from registered participants and pay the selected winners
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum TaskKind {
+    Standard,
+    Bounty { selection: WinnerSelection },
+}
+
+/// How budget votes are cast, chosen when voting starts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum BudgetBallot {
+    Open,                                  // Votes are visible as they are cast
+    CommitReveal { reveal_seconds: i64 },  // Hashed commits during voting, revealed afterwards
+}
+
+/// How the budget is computed from weighted votes, chosen when voting starts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum BudgetAggregation {
+    WeightedMedian,                 // Budget where cumulative weight crosses half
+    InterpolatedMedian,             // Weighted median interpolated between neighbouring budgets
+    TrimmedMean { trim_bps: u16 },  // Weighted mean after dropping trim_bps of weight from each end
+    CappedMedian { cap_bps: u16 },  // Weighted median with each voter capped at cap_bps of total weight
+}
+
+/// What happens to a budget vote outside the task's bounds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum OutOfRangeVote {
+    Reject,  // The vote fails
+    Clamp,   // The vote counts at the nearest bound
+}
+
+/// Range budget votes must fall in, relative to target_budget
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct BudgetBounds {
+    pub min_bps: u32,  // Lowest budget as basis points of target (2_500 = 25%)
+    pub max_bps: u32,  // Highest budget as basis points of target (40_000 = 400%)
+    pub out_of_range: OutOfRangeVote,
+}
+
+impl Default for BudgetBounds {
+    fn default() -> Self {
+        Self {
+            min_bps: DEFAULT_MIN_BUDGET_BPS,
+            max_bps: DEFAULT_MAX_BUDGET_BPS,
+            out_of_range: OutOfRangeVote::Reject,
+        }
+    }
+}
+
 pub const MAX_TASK_ID_LEN: usize = 64;
 pub const MAX_TASK_TITLE_LEN: usize = 100;
 pub const MAX_DELIVERABLES_LEN: usize = 2000;
 pub const MAX_PROOF_HASH_LEN: usize = 64;
 pub const MAX_PROOF_URI_LEN: usize = 200;
+pub const MAX_RECIPIENTS: usize = 5;
+pub const MAX_REVISIONS: u8 = 10;
+pub const MAX_REVIEWERS: usize = 3;
+pub const MAX_MILESTONES: usize = 5;
+pub const MAX_MILESTONE_TITLE_LEN: usize = 50;
+
+/// Budget voting period, unless set when voting starts: 7 days
+pub const DEFAULT_BUDGET_VOTING_SECONDS: i64 = 7 * 24 * 60 * 60;
+pub const MIN_BUDGET_VOTING_SECONDS: i64 = 24 * 60 * 60;
+pub const MAX_BUDGET_VOTING_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Reveal phase after a commit-reveal budget vote closes
+pub const MIN_BUDGET_REVEAL_SECONDS: i64 = 24 * 60 * 60;
+pub const MAX_BUDGET_REVEAL_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// Budget vote bounds, unless set when voting starts: 25%-400% of target
+pub const DEFAULT_MIN_BUDGET_BPS: u32 = 2_500;
+pub const DEFAULT_MAX_BUDGET_BPS: u32 = 40_000;
+
+/// Widest allowed upper bound: 10x target
+pub const MAX_BUDGET_BOUND_BPS: u32 = 100_000;
+
+/// Review window after proof submission, unless set at creation: 7 days
+pub const DEFAULT_REVIEW_WINDOW_SECONDS: i64 = 7 * 24 * 60 * 60;
+pub const MIN_REVIEW_WINDOW_SECONDS: i64 = 24 * 60 * 60;
+pub const MAX_REVIEW_WINDOW_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Time after the review window for a vote to be finalized before a silent
+/// review can be auto-approved: 2 days
+pub const REVIEW_FINALIZATION_GRACE_SECONDS: i64 = 2 * 24 * 60 * 60;
+
+/// Donor veto window after a hybrid reviewer approval: 3 days
+pub const VETO_WINDOW_SECONDS: i64 = 3 * 24 * 60 * 60;
+
+/// Basis points that a complete recipient split table must sum to
+pub const TOTAL_SHARE_BPS: u16 = 10_000;
+
+/// Planned milestone: a named slice of the deliverables and budget
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Milestone {
+    pub title: String,
+
+    /// Share of the budget in basis points
+    pub share_bps: u16,
+}
+
+impl Milestone {
+    pub const LEN: usize = 4 + MAX_MILESTONE_TITLE_LEN + 2;
+}
+
+/// Fixed payout share for one recipient of a jointly delivered task
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RecipientSplit {
+    /// Recipient wallet (must hold its own KYC attestation)
+    pub recipient: Pubkey,
+
+    /// Share of the payout in basis points
+    pub share_bps: u16,
+}
+
+impl RecipientSplit {
+    pub const LEN: usize = 32 + 2;
+}
 
 /// Task account
 /// PDA seeds: ["task", campaign.key(), task_id]
@@ -37,15 +171,39 @@ pub struct Task {
     /// Task creator (often same as campaign creator)
     pub creator: Pubkey,
 
+    /// Standard task or competitive bounty
+    pub kind: TaskKind,
+
     /// Recipient who will execute the task
     pub recipient: Option<Pubkey>,
 
+    /// Payout split table (empty = single recipient gets everything)
+    pub recipient_splits: Vec<RecipientSplit>,
+
+    /// Staked reviewers designated at creation (any one may decide)
+    pub reviewers: Vec<Pubkey>,
+
+    /// Approval model chosen at creation
+    pub approval_model: ApprovalModel,
+
+    /// Hybrid only: contribution share (bps) whose veto sends the task to Disputed
+    pub veto_threshold_bps: u16,
+
+    /// Hybrid only: end of the donor veto window after reviewer approval
+    pub veto_window_ends_at: Option<i64>,
+
     /// Task title
     pub title: String,
 
     /// Deliverables description
     pub deliverables: String,
 
+    /// Milestone layout (empty = single delivery)
+    pub milestones: Vec<Milestone>,
+
+    /// Skill tags from the tag registry (set once by the creator)
+    pub skill_tags: Vec<String>,
+
     /// Deadline timestamp (unix)
     pub deadline: Option<i64>,
 
@@ -67,6 +225,18 @@ pub struct Task {
     /// When budget voting started
     pub budget_voting_started_at: Option<i64>,
 
+    /// Length of the budget vote, chosen when voting starts
+    pub budget_voting_duration: i64,
+
+    /// Open or commit-reveal budget ballot, chosen when voting starts
+    pub budget_ballot: BudgetBallot,
+
+    /// Budget aggregation method, chosen when voting starts
+    pub budget_aggregation: BudgetAggregation,
+
+    /// Range budget votes must fall in, chosen when voting starts
+    pub budget_bounds: BudgetBounds,
+
     /// When budget was finalized
     pub budget_finalized_at: Option<i64>,
 
@@ -76,15 +246,21 @@ pub struct Task {
     /// When work started
     pub work_started_at: Option<i64>,
 
-    /// Proof hash (SHA256 of deliverable)
-    pub proof_hash: Option<String>,
-
-    /// Proof URI (IPFS/Arweave)
-    pub proof_uri: Option<String>,
-
     /// When proof submitted
     pub proof_submitted_at: Option<i64>,
 
+    /// Number of times reviewers sent the work back for changes
+    pub revision_count: u8,
+
+    /// Revisions allowed before a rejection becomes final
+    pub max_revisions: u8,
+
+    /// How long reviewers or donors have to act on a submission
+    pub review_window_seconds: i64,
+
+    /// Hash of the latest reviewer feedback
+    pub last_feedback_hash: Option<[u8; 32]>,
+
     /// When approved
     pub approved_at: Option<i64>,
 
@@ -100,9 +276,17 @@ impl Task {
         4 + MAX_TASK_ID_LEN +
         32 + // campaign
         32 + // creator
+        1 + 1 + // kind (enum with winner selection)
         1 + 32 + // recipient (Option<Pubkey>)
+        4 + MAX_RECIPIENTS * RecipientSplit::LEN + // recipient_splits
+        4 + MAX_REVIEWERS * 32 + // reviewers
+        1 + // approval_model (enum)
+        2 + // veto_threshold_bps
+        1 + 8 + // veto_window_ends_at
         4 + MAX_TASK_TITLE_LEN +
         4 + MAX_DELIVERABLES_LEN +
+        4 + MAX_MILESTONES * Milestone::LEN + // milestones
+        4 + MAX_SKILL_TAGS * (4 + MAX_SKILL_TAG_LEN) + // skill_tags
         1 + 8 + // deadline (Option<i64>)
         8 + // target_budget
         1 + 8 + // finalized_budget (Option<u64>)
@@ -110,12 +294,18 @@ impl Task {
         8 + // created_at
         8 + // updated_at
         1 + 8 + // budget_voting_started_at
+        8 + // budget_voting_duration
+        1 + 8 + // budget_ballot
+        1 + 2 + // budget_aggregation
+        4 + 4 + 1 + // budget_bounds
         1 + 8 + // budget_finalized_at
         1 + 8 + // funding_opened_at
         1 + 8 + // work_started_at
-        1 + 4 + MAX_PROOF_HASH_LEN + // proof_hash (Option<String>)
-        1 + 4 + MAX_PROOF_URI_LEN + // proof_uri (Option<String>)
         1 + 8 + // proof_submitted_at
+        1 + // revision_count
+        1 + // max_revisions
+        8 + // review_window_seconds
+        1 + 32 + // last_feedback_hash
         1 + 8 + // approved_at
         1 + 8 + // paid_out_at
         1; // bump
@@ -124,6 +314,11 @@ impl Task {
     pub fn can_transition_to(&self, new_state: &TaskState) -> bool {
         use TaskState::*;
 
+        // Bounties have no proof review: selecting winners approves them
+        if self.is_bounty() && matches!((&self.state, new_state), (Funded | InProgress, Approved)) {
+            return true;
+        }
+
         matches!(
             (&self.state, new_state),
             // From Draft
@@ -147,6 +342,7 @@ impl Task {
             (InProgress, Refunding) | // If deadline missed
 
             // From SubmittedForReview
+            (SubmittedForReview, InProgress) | // Changes requested
             (SubmittedForReview, Approved) |
             (SubmittedForReview, Rejected) |
             (SubmittedForReview, Disputed) |
@@ -169,6 +365,375 @@ impl Task {
         )
     }
 
+    /// Populate a freshly created task in Draft
+    pub fn initialize(
+        &mut self,
+        params: NewTask,
+        campaign: Pubkey,
+        creator: Pubkey,
+        now: i64,
+        bump: u8,
+    ) {
+        self.task_id = params.task_id;
+        self.campaign = campaign;
+        self.creator = creator;
+        self.kind = params.kind;
+        self.recipient = params.recipient;
+        self.recipient_splits = Vec::new();
+        self.reviewers = params.reviewers;
+        self.approval_model = params.approval_model;
+        self.veto_threshold_bps = params.veto_threshold_bps;
+        self.veto_window_ends_at = None;
+        self.title = params.title;
+        self.deliverables = params.deliverables;
+        self.milestones = params.milestones;
+        self.skill_tags = Vec::new();
+        self.target_budget = params.target_budget;
+        self.deadline = params.deadline;
+        self.budget_voting_duration = 0;
+        self.budget_ballot = BudgetBallot::Open;
+        self.budget_aggregation = BudgetAggregation::WeightedMedian;
+        self.budget_bounds = BudgetBounds::default();
+        self.revision_count = 0;
+        self.max_revisions = params.max_revisions;
+        self.review_window_seconds = params.review_window_seconds;
+        self.state = TaskState::Draft;
+        self.created_at = now;
+        self.updated_at = now;
+        self.bump = bump;
+    }
+
+    /// Recipient splits are locked once funding opens
+    pub fn can_set_recipient_splits(&self) -> bool {
+        !self.is_bounty()
+            && matches!(
+                self.state,
+                TaskState::Draft | TaskState::VotingBudget | TaskState::BudgetFinalized
+            )
+    }
+
+    /// Validate a recipient split table: 1..=MAX_RECIPIENTS unique recipients,
+    /// non-zero shares summing to exactly TOTAL_SHARE_BPS
+    pub fn validate_recipient_splits(splits: &[RecipientSplit]) -> bool {
+        if splits.is_empty() || splits.len() > MAX_RECIPIENTS {
+            return false;
+        }
+
+        let mut total: u32 = 0;
+        for (i, split) in splits.iter().enumerate() {
+            if split.share_bps == 0 {
+                return false;
+            }
+            if splits[..i].iter().any(|s| s.recipient == split.recipient) {
+                return false;
+            }
+            total += split.share_bps as u32;
+        }
+
+        total == TOTAL_SHARE_BPS as u32
+    }
+
+    /// Split a payout across the recipient table by basis points.
+    /// Rounding dust goes to the first (lead) recipient so shares sum to `amount`.
+    pub fn split_payout(&self, amount: u64) -> Vec<u64> {
+        let mut shares: Vec<u64> = self
+            .recipient_splits
+            .iter()
+            .map(|s| ((amount as u128 * s.share_bps as u128) / TOTAL_SHARE_BPS as u128) as u64)
+            .collect();
+
+        let distributed: u64 = shares.iter().sum();
+        if let Some(first) = shares.first_mut() {
+            *first += amount - distributed;
+        }
+
+        shares
+    }
+
+    /// Check if key is one of the task's designated reviewers
+    pub fn is_reviewer(&self, key: &Pubkey) -> bool {
+        self.reviewers.contains(key)
+    }
+
+    /// Validate milestone layout: empty, or up to MAX_MILESTONES named
+    /// milestones with non-zero shares summing to TOTAL_SHARE_BPS
+    pub fn validate_milestones(milestones: &[Milestone]) -> bool {
+        if milestones.is_empty() {
+            return true;
+        }
+
+        milestones.len() <= MAX_MILESTONES
+            && milestones.iter().all(|m| {
+                !m.title.is_empty() && m.title.len() <= MAX_MILESTONE_TITLE_LEN && m.share_bps > 0
+            })
+            && milestones.iter().map(|m| m.share_bps as u32).sum::<u32>() == TOTAL_SHARE_BPS as u32
+    }
+
+    /// Validate reviewer pool: at most MAX_REVIEWERS, no duplicates
+    pub fn validate_reviewers(reviewers: &[Pubkey]) -> bool {
+        reviewers.len() <= MAX_REVIEWERS
+            && reviewers
+                .iter()
+                .enumerate()
+                .all(|(i, r)| !reviewers[..i].contains(r))
+    }
+
+    /// Validate approval settings: reviewer models need a reviewer pool,
+    /// hybrid needs a veto threshold in (0, 10000] bps
+    pub fn validate_approval_model(
+        model: &ApprovalModel,
+        reviewers: &[Pubkey],
+        veto_threshold_bps: u16,
+    ) -> bool {
+        match model {
+            ApprovalModel::DonorVote => true,
+            ApprovalModel::Reviewer => !reviewers.is_empty(),
+            ApprovalModel::Hybrid => {
+                !reviewers.is_empty()
+                    && veto_threshold_bps > 0
+                    && veto_threshold_bps <= TOTAL_SHARE_BPS
+            }
+        }
+    }
+
+    /// PDA that must sign approve/reject decisions for this task:
+    /// the dispute while disputed, otherwise the approval model's decision account
+    /// (reviewer decisions are keyed by review round)
+    pub fn approval_authority(&self, task_key: &Pubkey) -> Pubkey {
+        let round = [self.revision_count];
+        if self.state == TaskState::Disputed {
+            return Self::dispute_authority(task_key);
+        }
+        let (seeds, program_id): (Vec<&[u8]>, Pubkey) = {
+            match self.approval_model {
+                ApprovalModel::DonorVote => {
+                    (vec![b"approval_tally", task_key.as_ref()], approval_vote_program::ID)
+                }
+                ApprovalModel::Reviewer | ApprovalModel::Hybrid => (
+                    vec![b"review_decision", task_key.as_ref(), &round],
+                    reviewer_registry_program::ID,
+                ),
+            }
+        };
+
+        Pubkey::find_program_address(&seeds, &program_id).0
+    }
+
+    /// PDA that must sign moving a task into a dispute (dispute_module's dispute)
+    pub fn dispute_authority(task_key: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"dispute", task_key.as_ref()],
+            &dispute_module_program::ID,
+        )
+        .0
+    }
+
+    /// PDA that must sign a donor veto (approval_vote's veto tally)
+    pub fn veto_authority(task_key: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"veto_tally", task_key.as_ref()],
+            &approval_vote_program::ID,
+        )
+        .0
+    }
+
+    /// PDA that must sign a proof submission (proof_registry's proof account)
+    pub fn proof_authority(task_key: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"proof", task_key.as_ref()],
+            &proof_registry_program::ID,
+        )
+        .0
+    }
+
+    /// PDA that must sign a bounty entry submission (proof_registry's entry proof account)
+    pub fn entry_proof_authority(task_key: &Pubkey, participant: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"entry_proof", task_key.as_ref(), participant.as_ref()],
+            &proof_registry_program::ID,
+        )
+        .0
+    }
+
+    /// PDA that must sign the move to PaidOut (task_escrow's escrow)
+    pub fn payout_authority(task_key: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"escrow", task_key.as_ref()], &task_escrow_program::ID).0
+    }
+
+    /// PDA that must sign budget finalization (budget_vote's aggregate)
+    pub fn budget_finalization_authority(task_key: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"budget_aggregate", task_key.as_ref()],
+            &budget_vote_program::ID,
+        )
+        .0
+    }
+
+    /// PDA that must sign a donor-approved deadline extension
+    pub fn deadline_extension_authority(task_key: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"deadline_extension", task_key.as_ref()],
+            &approval_vote_program::ID,
+        )
+        .0
+    }
+
+    /// Deadline can be extended while work is pending and the current deadline has not passed
+    pub fn can_extend_deadline(&self, current_time: i64) -> bool {
+        self.deadline.is_some()
+            && !self.is_past_deadline(current_time)
+            && matches!(
+                self.state,
+                TaskState::FundingOpen | TaskState::Funded | TaskState::InProgress
+            )
+    }
+
+    /// Open for recipients to pick up: no recipient yet, funding opened and
+    /// not past the working stage (listed in the skill tag indexes while
+    /// true). Drafts and tasks still settling their budget aren't listed, so
+    /// tasks that never get going can't crowd an index.
+    pub fn needs_recipient(&self) -> bool {
+        self.recipient.is_none()
+            && matches!(
+                self.state,
+                TaskState::FundingOpen | TaskState::Funded | TaskState::InProgress
+            )
+    }
+
+    pub fn is_bounty(&self) -> bool {
+        matches!(self.kind, TaskKind::Bounty { .. })
+    }
+
+    /// Bounty entries are accepted until the deadline while the task is
+    /// raising funds or under way
+    pub fn can_accept_bounty_entries(&self, current_time: i64) -> bool {
+        self.is_bounty()
+            && self.deadline.is_some()
+            && !self.is_past_deadline(current_time)
+            && matches!(
+                self.state,
+                TaskState::FundingOpen | TaskState::Funded | TaskState::InProgress
+            )
+    }
+
+    /// Winners are picked once the entry deadline has passed on a funded bounty
+    pub fn can_select_winners(&self, current_time: i64) -> bool {
+        self.is_bounty()
+            && self.is_past_deadline(current_time)
+            && matches!(self.state, TaskState::Funded | TaskState::InProgress)
+    }
+
+    /// Whether `signer` may pick this bounty's winners
+    pub fn is_winner_selector(&self, task_key: &Pubkey, signer: &Pubkey) -> bool {
+        match &self.kind {
+            TaskKind::Standard => false,
+            TaskKind::Bounty { selection } => match selection {
+                WinnerSelection::Creator => *signer == self.creator,
+                WinnerSelection::Reviewer => self.is_reviewer(signer),
+                WinnerSelection::DonorVote => *signer == Self::bounty_selection_authority(task_key),
+            },
+        }
+    }
+
+    /// PDA that must sign a donor-voted bounty selection (approval_vote's bounty tally)
+    pub fn bounty_selection_authority(task_key: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"bounty_tally", task_key.as_ref()],
+            &approval_vote_program::ID,
+        )
+        .0
+    }
+
+    /// Check if a hybrid approval is waiting out its veto window
+    pub fn is_veto_window_open(&self, current_time: i64) -> bool {
+        matches!(self.veto_window_ends_at, Some(ends_at) if current_time <= ends_at)
+    }
+
+    /// Whether a rejection sends the work back for revision instead of
+    /// ending the task (revision limit not reached, deadline not passed)
+    pub fn can_request_changes(&self, current_time: i64) -> bool {
+        self.state == TaskState::SubmittedForReview
+            && self.revision_count < self.max_revisions
+            && !self.is_past_deadline(current_time)
+    }
+
+    /// End of the budget vote, once voting has started
+    pub fn budget_voting_ends_at(&self) -> Option<i64> {
+        self.budget_voting_started_at
+            .map(|started_at| started_at + self.budget_voting_duration)
+    }
+
+    /// End of the reveal phase for a commit-reveal budget vote; the same
+    /// as the end of voting for an open ballot
+    pub fn budget_reveal_ends_at(&self) -> Option<i64> {
+        let ends_at = self.budget_voting_ends_at()?;
+        match self.budget_ballot {
+            BudgetBallot::Open => Some(ends_at),
+            BudgetBallot::CommitReveal { reveal_seconds } => Some(ends_at + reveal_seconds),
+        }
+    }
+
+    pub fn validate_budget_ballot(ballot: &BudgetBallot) -> bool {
+        match ballot {
+            BudgetBallot::Open => true,
+            BudgetBallot::CommitReveal { reveal_seconds } => {
+                (MIN_BUDGET_REVEAL_SECONDS..=MAX_BUDGET_REVEAL_SECONDS).contains(reveal_seconds)
+            }
+        }
+    }
+
+    /// Trimmed means must keep some weight; caps must allow some weight
+    pub fn validate_budget_aggregation(aggregation: &BudgetAggregation) -> bool {
+        match aggregation {
+            BudgetAggregation::WeightedMedian | BudgetAggregation::InterpolatedMedian => true,
+            BudgetAggregation::TrimmedMean { trim_bps } => *trim_bps < TOTAL_SHARE_BPS / 2,
+            BudgetAggregation::CappedMedian { cap_bps } => (1..=TOTAL_SHARE_BPS).contains(cap_bps),
+        }
+    }
+
+    /// Bounds must contain the target itself
+    pub fn validate_budget_bounds(bounds: &BudgetBounds) -> bool {
+        bounds.min_bps <= TOTAL_SHARE_BPS as u32
+            && bounds.max_bps >= TOTAL_SHARE_BPS as u32
+            && bounds.max_bps <= MAX_BUDGET_BOUND_BPS
+    }
+
+    /// Lowest and highest budget a vote may propose
+    pub fn budget_range(&self) -> (u64, u64) {
+        let bound = |bps: u32| {
+            let amount = self.target_budget as u128 * bps as u128 / TOTAL_SHARE_BPS as u128;
+            amount.min(u64::MAX as u128) as u64
+        };
+        (bound(self.budget_bounds.min_bps), bound(self.budget_bounds.max_bps))
+    }
+
+    /// The budget a vote counts at: itself when in range, the nearest bound
+    /// when clamping, None when out-of-range votes are rejected
+    pub fn bounded_budget(&self, proposed_budget: u64) -> Option<u64> {
+        let (min, max) = self.budget_range();
+        match self.budget_bounds.out_of_range {
+            OutOfRangeVote::Reject => (min..=max).contains(&proposed_budget).then_some(proposed_budget),
+            OutOfRangeVote::Clamp => Some(proposed_budget.clamp(min, max)),
+        }
+    }
+
+    /// End of the review window for the current submission
+    pub fn review_window_ends_at(&self) -> Option<i64> {
+        self.proof_submitted_at
+            .map(|submitted_at| submitted_at + self.review_window_seconds)
+    }
+
+    /// A submission nobody rejected, disputed or finalized can be approved
+    /// once the review window and the finalization grace period have lapsed
+    pub fn can_finalize_review(&self, current_time: i64) -> bool {
+        self.state == TaskState::SubmittedForReview
+            && self.veto_window_ends_at.is_none()
+            && matches!(
+                self.review_window_ends_at(),
+                Some(ends_at) if current_time > ends_at + REVIEW_FINALIZATION_GRACE_SECONDS
+            )
+    }
+
     /// Check if deadline has passed
     pub fn is_past_deadline(&self, current_time: i64) -> bool {
         if let Some(deadline) = self.deadline {
@@ -195,19 +760,138 @@ impl Task {
     }
 }
 
+/// Creation parameters shared by create_task and create_task_from_template
+pub struct NewTask {
+    pub task_id: String,
+    pub title: String,
+    pub deliverables: String,
+    pub milestones: Vec<Milestone>,
+    pub target_budget: u64,
+    pub recipient: Option<Pubkey>,
+    pub deadline: Option<i64>,
+    pub max_revisions: u8,
+    pub review_window_seconds: i64,
+    pub reviewers: Vec<Pubkey>,
+    pub approval_model: ApprovalModel,
+    pub veto_threshold_bps: u16,
+    pub kind: TaskKind,
+}
+
+impl NewTask {
+    pub fn validate(&self) -> Result<()> {
+        require!(Task::validate_task_id(&self.task_id), TaskError::InvalidTaskId);
+        require!(Task::validate_title(&self.title), TaskError::InvalidTitle);
+        require!(Task::validate_deliverables(&self.deliverables), TaskError::InvalidDeliverables);
+        require!(Task::validate_milestones(&self.milestones), TaskError::InvalidMilestones);
+        require!(self.max_revisions <= MAX_REVISIONS, TaskError::InvalidMaxRevisions);
+        require!(
+            (MIN_REVIEW_WINDOW_SECONDS..=MAX_REVIEW_WINDOW_SECONDS).contains(&self.review_window_seconds),
+            TaskError::InvalidReviewWindow
+        );
+        require!(Task::validate_reviewers(&self.reviewers), TaskError::InvalidReviewers);
+        require!(
+            Task::validate_approval_model(&self.approval_model, &self.reviewers, self.veto_threshold_bps),
+            TaskError::InvalidApprovalModel
+        );
+        if let TaskKind::Bounty { selection } = &self.kind {
+            require!(
+                self.deadline.is_some()
+                    && self.recipient.is_none()
+                    && (*selection != WinnerSelection::Reviewer || !self.reviewers.is_empty()),
+                TaskError::InvalidBountyConfig
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Instruction arguments for create_task (task_id is passed separately for PDA seeds)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateTaskParams {
+    pub title: String,
+    pub deliverables: String,
+    pub target_budget: u64,
+    pub recipient: Option<Pubkey>,
+    pub deadline: Option<i64>,
+    pub max_revisions: u8,
+    pub reviewers: Vec<Pubkey>,
+    pub approval_model: ApprovalModel,
+    pub veto_threshold_bps: u16,
+    pub review_window_seconds: Option<i64>,
+}
+
+impl CreateTaskParams {
+    pub fn into_new_task(self, task_id: String) -> NewTask {
+        NewTask {
+            task_id,
+            title: self.title,
+            deliverables: self.deliverables,
+            milestones: Vec::new(),
+            target_budget: self.target_budget,
+            recipient: self.recipient,
+            deadline: self.deadline,
+            max_revisions: self.max_revisions,
+            review_window_seconds: self.review_window_seconds.unwrap_or(DEFAULT_REVIEW_WINDOW_SECONDS),
+            reviewers: self.reviewers,
+            approval_model: self.approval_model,
+            veto_threshold_bps: self.veto_threshold_bps,
+            kind: TaskKind::Standard,
+        }
+    }
+}
+
+/// Instruction arguments for create_bounty (task_id is passed separately for PDA seeds)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateBountyParams {
+    pub title: String,
+    pub deliverables: String,
+    pub target_budget: u64,
+    pub deadline: i64,
+    pub reviewers: Vec<Pubkey>,
+    pub selection: WinnerSelection,
+}
+
+impl CreateBountyParams {
+    pub fn into_new_task(self, task_id: String) -> NewTask {
+        NewTask {
+            task_id,
+            title: self.title,
+            deliverables: self.deliverables,
+            milestones: Vec::new(),
+            target_budget: self.target_budget,
+            recipient: None,
+            deadline: Some(self.deadline),
+            max_revisions: 0,
+            review_window_seconds: DEFAULT_REVIEW_WINDOW_SECONDS,
+            reviewers: self.reviewers,
+            approval_model: ApprovalModel::DonorVote,
+            veto_threshold_bps: 0,
+            kind: TaskKind::Bounty { selection: self.selection },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_state_transitions() {
-        let task = Task {
+    fn test_task() -> Task {
+        Task {
             task_id: "test".to_string(),
             campaign: Pubkey::default(),
             creator: Pubkey::default(),
+            kind: TaskKind::Standard,
             recipient: None,
+            recipient_splits: vec![],
+            reviewers: vec![],
+            approval_model: ApprovalModel::DonorVote,
+            veto_threshold_bps: 0,
+            veto_window_ends_at: None,
             title: "Test".to_string(),
             deliverables: "Test".to_string(),
+            milestones: vec![],
+            skill_tags: vec![],
             deadline: None,
             target_budget: 100,
             finalized_budget: None,
@@ -215,16 +899,27 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             budget_voting_started_at: None,
+            budget_voting_duration: 0,
+            budget_ballot: BudgetBallot::Open,
+            budget_aggregation: BudgetAggregation::WeightedMedian,
+            budget_bounds: BudgetBounds::default(),
             budget_finalized_at: None,
             funding_opened_at: None,
             work_started_at: None,
-            proof_hash: None,
-            proof_uri: None,
             proof_submitted_at: None,
+            revision_count: 0,
+            max_revisions: 0,
+            review_window_seconds: DEFAULT_REVIEW_WINDOW_SECONDS,
+            last_feedback_hash: None,
             approved_at: None,
             paid_out_at: None,
             bump: 0,
-        };
+        }
+    }
+
+    #[test]
+    fn test_state_transitions() {
+        let task = test_task();
 
         // Valid transitions
         assert!(task.can_transition_to(&TaskState::VotingBudget));
@@ -236,4 +931,304 @@ mod tests {
         // Can always dispute
         assert!(task.can_transition_to(&TaskState::Disputed));
     }
+
+    #[test]
+    fn test_recipient_split_validation() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+
+        assert!(Task::validate_recipient_splits(&[
+            RecipientSplit { recipient: a, share_bps: 6_000 },
+            RecipientSplit { recipient: b, share_bps: 4_000 },
+        ]));
+
+        // Must sum to 10 000 bps
+        assert!(!Task::validate_recipient_splits(&[
+            RecipientSplit { recipient: a, share_bps: 6_000 },
+            RecipientSplit { recipient: b, share_bps: 3_000 },
+        ]));
+
+        // Duplicate recipients
+        assert!(!Task::validate_recipient_splits(&[
+            RecipientSplit { recipient: a, share_bps: 5_000 },
+            RecipientSplit { recipient: a, share_bps: 5_000 },
+        ]));
+
+        // Zero share
+        assert!(!Task::validate_recipient_splits(&[
+            RecipientSplit { recipient: a, share_bps: 10_000 },
+            RecipientSplit { recipient: b, share_bps: 0 },
+        ]));
+
+        // Empty and oversized tables
+        assert!(!Task::validate_recipient_splits(&[]));
+        let too_many: Vec<RecipientSplit> = (0..MAX_RECIPIENTS + 1)
+            .map(|_| RecipientSplit { recipient: Pubkey::new_unique(), share_bps: 1 })
+            .collect();
+        assert!(!Task::validate_recipient_splits(&too_many));
+    }
+
+    #[test]
+    fn test_milestone_layout() {
+        let milestone = |title: &str, share_bps| Milestone { title: title.to_string(), share_bps };
+
+        assert!(Task::validate_milestones(&[]));
+        assert!(Task::validate_milestones(&[milestone("Draft", 4_000), milestone("Final", 6_000)]));
+        assert!(!Task::validate_milestones(&[milestone("Draft", 4_000), milestone("Final", 5_000)]));
+        assert!(!Task::validate_milestones(&[milestone("", 10_000)]));
+        assert!(!Task::validate_milestones(&[milestone("Draft", 0), milestone("Final", 10_000)]));
+    }
+
+    #[test]
+    fn test_reviewer_pool() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+
+        assert!(Task::validate_reviewers(&[]));
+        assert!(Task::validate_reviewers(&[a, b]));
+        assert!(!Task::validate_reviewers(&[a, a]));
+        assert!(!Task::validate_reviewers(&[a, b, Pubkey::new_unique(), Pubkey::new_unique()]));
+
+        let mut task = test_task();
+        task.reviewers = vec![a];
+        assert!(task.is_reviewer(&a));
+        assert!(!task.is_reviewer(&b));
+    }
+
+    #[test]
+    fn test_approval_model_settings() {
+        let reviewer = [Pubkey::new_unique()];
+
+        assert!(Task::validate_approval_model(&ApprovalModel::DonorVote, &[], 0));
+        assert!(Task::validate_approval_model(&ApprovalModel::Reviewer, &reviewer, 0));
+        assert!(!Task::validate_approval_model(&ApprovalModel::Reviewer, &[], 0));
+        assert!(Task::validate_approval_model(&ApprovalModel::Hybrid, &reviewer, 3_000));
+        assert!(!Task::validate_approval_model(&ApprovalModel::Hybrid, &reviewer, 0));
+        assert!(!Task::validate_approval_model(&ApprovalModel::Hybrid, &reviewer, 10_001));
+    }
+
+    #[test]
+    fn test_approval_authority_follows_model() {
+        let task_key = Pubkey::new_unique();
+        let mut task = test_task();
+        task.state = TaskState::SubmittedForReview;
+
+        let donor_vote = task.approval_authority(&task_key);
+        task.approval_model = ApprovalModel::Reviewer;
+        let reviewer = task.approval_authority(&task_key);
+        task.approval_model = ApprovalModel::Hybrid;
+        assert_eq!(task.approval_authority(&task_key), reviewer);
+        assert_ne!(donor_vote, reviewer);
+
+        // Each review round has its own decision account
+        task.revision_count = 1;
+        assert_ne!(task.approval_authority(&task_key), reviewer);
+        task.revision_count = 0;
+
+        task.state = TaskState::Disputed;
+        let dispute = task.approval_authority(&task_key);
+        assert_ne!(dispute, reviewer);
+        assert_ne!(dispute, donor_vote);
+    }
+
+    #[test]
+    fn test_deadline_extension_window() {
+        let mut task = test_task();
+        task.state = TaskState::InProgress;
+        assert!(!task.can_extend_deadline(0)); // no deadline to extend
+
+        task.deadline = Some(100);
+        assert!(task.can_extend_deadline(100));
+        assert!(!task.can_extend_deadline(101));
+
+        task.state = TaskState::SubmittedForReview;
+        assert!(!task.can_extend_deadline(0));
+    }
+
+    #[test]
+    fn test_veto_window() {
+        let mut task = test_task();
+        assert!(!task.is_veto_window_open(0));
+
+        task.veto_window_ends_at = Some(100);
+        assert!(task.is_veto_window_open(100));
+        assert!(!task.is_veto_window_open(101));
+    }
+
+    #[test]
+    fn test_revision_limit() {
+        let mut task = test_task();
+        task.state = TaskState::SubmittedForReview;
+        task.max_revisions = 2;
+        task.deadline = Some(1_000);
+
+        assert!(task.can_request_changes(0));
+        assert!(task.can_transition_to(&TaskState::InProgress));
+
+        // Revisions exhausted: rejection is final
+        task.revision_count = 2;
+        assert!(!task.can_request_changes(0));
+
+        // Deadline passed: rejection is final
+        task.revision_count = 0;
+        assert!(!task.can_request_changes(1_001));
+
+        // Only submitted work can be sent back
+        task.state = TaskState::Disputed;
+        assert!(!task.can_request_changes(0));
+    }
+
+    #[test]
+    fn test_split_payout_sums_to_amount() {
+        let mut task = test_task();
+        task.recipient_splits = vec![
+            RecipientSplit { recipient: Pubkey::new_unique(), share_bps: 3_333 },
+            RecipientSplit { recipient: Pubkey::new_unique(), share_bps: 3_333 },
+            RecipientSplit { recipient: Pubkey::new_unique(), share_bps: 3_334 },
+        ];
+
+        let shares = task.split_payout(1_000_000_001);
+        assert_eq!(shares.iter().sum::<u64>(), 1_000_000_001);
+        assert_eq!(shares[1], 333_300_000);
+        assert_eq!(shares[2], 333_400_000);
+
+        // No overflow at the top of the range
+        let shares = task.split_payout(u64::MAX);
+        assert_eq!(shares.iter().sum::<u64>(), u64::MAX);
+    }
+
+    #[test]
+    fn test_bounty_windows_and_selector() {
+        let mut task = test_task();
+        let task_key = Pubkey::new_unique();
+        let reviewer = Pubkey::new_unique();
+        task.reviewers = vec![reviewer];
+        task.deadline = Some(100);
+        task.state = TaskState::Funded;
+        assert!(!task.can_accept_bounty_entries(50)); // standard task
+        assert!(!task.can_transition_to(&TaskState::Approved));
+
+        task.kind = TaskKind::Bounty { selection: WinnerSelection::Reviewer };
+        assert!(task.can_accept_bounty_entries(100));
+        assert!(!task.can_select_winners(100));
+        assert!(!task.can_accept_bounty_entries(101));
+        assert!(task.can_select_winners(101));
+        assert!(!task.can_set_recipient_splits());
+
+        // Winner selection approves a bounty without a review
+        assert!(task.can_transition_to(&TaskState::Approved));
+        task.state = TaskState::InProgress;
+        assert!(task.can_transition_to(&TaskState::Approved));
+        task.state = TaskState::FundingOpen;
+        assert!(!task.can_transition_to(&TaskState::Approved));
+        task.state = TaskState::Funded;
+
+        assert!(task.is_winner_selector(&task_key, &reviewer));
+        assert!(!task.is_winner_selector(&task_key, &task.creator.clone()));
+
+        task.kind = TaskKind::Bounty { selection: WinnerSelection::DonorVote };
+        assert!(!task.is_winner_selector(&task_key, &reviewer));
+        assert!(task.is_winner_selector(&task_key, &Task::bounty_selection_authority(&task_key)));
+    }
+
+    #[test]
+    fn test_needs_recipient() {
+        let mut task = test_task();
+        for state in [TaskState::Draft, TaskState::VotingBudget, TaskState::BudgetFinalized] {
+            task.state = state;
+            assert!(!task.needs_recipient());
+        }
+
+        task.state = TaskState::FundingOpen;
+        assert!(task.needs_recipient());
+
+        task.state = TaskState::SubmittedForReview;
+        assert!(!task.needs_recipient());
+
+        task.state = TaskState::Funded;
+        task.recipient = Some(Pubkey::new_unique());
+        assert!(!task.needs_recipient());
+    }
+
+    #[test]
+    fn test_silent_review_finalization() {
+        let mut task = test_task();
+        task.state = TaskState::SubmittedForReview;
+        task.proof_submitted_at = Some(1_000);
+        let lapsed = 1_000 + DEFAULT_REVIEW_WINDOW_SECONDS + REVIEW_FINALIZATION_GRACE_SECONDS;
+
+        assert_eq!(task.review_window_ends_at(), Some(1_000 + DEFAULT_REVIEW_WINDOW_SECONDS));
+        assert!(!task.can_finalize_review(lapsed));
+        assert!(task.can_finalize_review(lapsed + 1));
+
+        // Hybrid approval waiting out its veto window
+        task.veto_window_ends_at = Some(lapsed + 10);
+        assert!(!task.can_finalize_review(lapsed + 1));
+
+        task.veto_window_ends_at = None;
+        task.state = TaskState::Disputed;
+        assert!(!task.can_finalize_review(lapsed + 1));
+    }
+
+    #[test]
+    fn test_budget_ballot_phases() {
+        let mut task = test_task();
+        assert_eq!(task.budget_reveal_ends_at(), None);
+
+        task.budget_voting_started_at = Some(1_000);
+        task.budget_voting_duration = DEFAULT_BUDGET_VOTING_SECONDS;
+        let voting_ends_at = 1_000 + DEFAULT_BUDGET_VOTING_SECONDS;
+        assert_eq!(task.budget_reveal_ends_at(), Some(voting_ends_at));
+
+        task.budget_ballot = BudgetBallot::CommitReveal { reveal_seconds: MIN_BUDGET_REVEAL_SECONDS };
+        assert_eq!(task.budget_voting_ends_at(), Some(voting_ends_at));
+        assert_eq!(task.budget_reveal_ends_at(), Some(voting_ends_at + MIN_BUDGET_REVEAL_SECONDS));
+
+        assert!(Task::validate_budget_ballot(&BudgetBallot::Open));
+        assert!(Task::validate_budget_ballot(&task.budget_ballot));
+        assert!(!Task::validate_budget_ballot(&BudgetBallot::CommitReveal { reveal_seconds: 0 }));
+        assert!(!Task::validate_budget_ballot(&BudgetBallot::CommitReveal {
+            reveal_seconds: MAX_BUDGET_REVEAL_SECONDS + 1,
+        }));
+    }
+
+    #[test]
+    fn test_budget_bounds() {
+        let mut task = test_task();
+        task.target_budget = 1_000;
+        assert_eq!(task.budget_range(), (250, 4_000));
+
+        assert_eq!(task.bounded_budget(250), Some(250));
+        assert_eq!(task.bounded_budget(4_000), Some(4_000));
+        assert_eq!(task.bounded_budget(0), None);
+        assert_eq!(task.bounded_budget(u64::MAX), None);
+
+        task.budget_bounds.out_of_range = OutOfRangeVote::Clamp;
+        assert_eq!(task.bounded_budget(0), Some(250));
+        assert_eq!(task.bounded_budget(u64::MAX), Some(4_000));
+        assert_eq!(task.bounded_budget(1_500), Some(1_500));
+
+        // Huge targets saturate rather than overflow
+        task.target_budget = u64::MAX;
+        assert_eq!(task.budget_range().1, u64::MAX);
+
+        assert!(Task::validate_budget_bounds(&BudgetBounds::default()));
+        let bounds = |min_bps, max_bps| BudgetBounds { min_bps, max_bps, out_of_range: OutOfRangeVote::Reject };
+        assert!(Task::validate_budget_bounds(&bounds(10_000, 10_000)));
+        assert!(!Task::validate_budget_bounds(&bounds(10_001, 20_000)));
+        assert!(!Task::validate_budget_bounds(&bounds(5_000, 9_999)));
+        assert!(!Task::validate_budget_bounds(&bounds(5_000, MAX_BUDGET_BOUND_BPS + 1)));
+    }
+
+    #[test]
+    fn test_budget_aggregation_settings() {
+        assert!(Task::validate_budget_aggregation(&BudgetAggregation::WeightedMedian));
+        assert!(Task::validate_budget_aggregation(&BudgetAggregation::InterpolatedMedian));
+        assert!(Task::validate_budget_aggregation(&BudgetAggregation::TrimmedMean { trim_bps: 0 }));
+        assert!(Task::validate_budget_aggregation(&BudgetAggregation::TrimmedMean { trim_bps: 4_999 }));
+        assert!(!Task::validate_budget_aggregation(&BudgetAggregation::TrimmedMean { trim_bps: 5_000 }));
+        assert!(Task::validate_budget_aggregation(&BudgetAggregation::CappedMedian { cap_bps: 1_000 }));
+        assert!(!Task::validate_budget_aggregation(&BudgetAggregation::CappedMedian { cap_bps: 0 }));
+        assert!(!Task::validate_budget_aggregation(&BudgetAggregation::CappedMedian { cap_bps: 10_001 }));
+    }
 }
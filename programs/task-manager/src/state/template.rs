@@ -0,0 +1,282 @@
+use anchor_lang::prelude::*;
+use crate::error::TaskError;
+use crate::state::*;
+
+/// Placeholder in a title pattern replaced by the per-task parameter
+pub const TITLE_PLACEHOLDER: &str = "{}";
+
+/// Longest default deadline a template may set: 365 days after creation
+pub const MAX_DEADLINE_OFFSET_SECONDS: i64 = 365 * 24 * 60 * 60;
+
+/// Reusable task template
+/// PDA seeds: ["task_template", creator, template_id]
+#[account]
+pub struct TaskTemplate {
+    /// Identifier within the creator's templates
+    pub template_id: String,
+
+    /// Template owner
+    pub creator: Pubkey,
+
+    /// Title with an optional "{}" placeholder, e.g. "Translate docs into {}"
+    pub title_pattern: String,
+
+    /// Default deliverables description
+    pub deliverables: String,
+
+    /// Default deadline in seconds after task creation (None = no deadline)
+    pub default_deadline_offset: Option<i64>,
+
+    /// Default approval model
+    pub approval_model: ApprovalModel,
+
+    /// Default hybrid veto threshold
+    pub veto_threshold_bps: u16,
+
+    /// Default revision limit
+    pub max_revisions: u8,
+
+    /// Default review window after proof submission
+    pub review_window_seconds: i64,
+
+    /// Default reviewer pool
+    pub reviewers: Vec<Pubkey>,
+
+    /// Milestone layout copied into each task
+    pub milestones: Vec<Milestone>,
+
+    /// Tasks instantiated from this template
+    pub tasks_created: u32,
+
+    /// When created
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Per-task overrides of template defaults
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct TemplateOverrides {
+    pub deliverables: Option<String>,
+    pub deadline: Option<i64>,
+    pub max_revisions: Option<u8>,
+    pub reviewers: Option<Vec<Pubkey>>,
+    pub milestones: Option<Vec<Milestone>>,
+    pub approval_model: Option<ApprovalModel>,
+    pub veto_threshold_bps: Option<u16>,
+    pub review_window_seconds: Option<i64>,
+}
+
+impl TaskTemplate {
+    pub const LEN: usize = 8 + // discriminator
+        4 + MAX_TASK_ID_LEN + // template_id
+        32 + // creator
+        4 + MAX_TASK_TITLE_LEN + // title_pattern
+        4 + MAX_DELIVERABLES_LEN + // deliverables
+        1 + 8 + // default_deadline_offset
+        1 + // approval_model
+        2 + // veto_threshold_bps
+        1 + // max_revisions
+        8 + // review_window_seconds
+        4 + MAX_REVIEWERS * 32 + // reviewers
+        4 + MAX_MILESTONES * Milestone::LEN + // milestones
+        4 + // tasks_created
+        8 + // created_at
+        1; // bump
+
+    /// Validate title pattern
+    pub fn validate_title_pattern(pattern: &str) -> bool {
+        !pattern.is_empty() && pattern.len() <= MAX_TASK_TITLE_LEN
+    }
+
+    /// Fill the title placeholder (first occurrence only)
+    pub fn render_title(&self, title_param: &str) -> String {
+        self.title_pattern.replacen(TITLE_PLACEHOLDER, title_param, 1)
+    }
+
+    /// Build task creation parameters from template defaults plus overrides
+    /// Fails if the default deadline offset overflows the creation time
+    pub fn instantiate(
+        &self,
+        task_id: String,
+        title_param: &str,
+        target_budget: u64,
+        recipient: Option<Pubkey>,
+        overrides: TemplateOverrides,
+        now: i64,
+    ) -> Result<NewTask> {
+        let deadline = match overrides.deadline {
+            Some(deadline) => Some(deadline),
+            None => match self.default_deadline_offset {
+                Some(offset) => Some(now.checked_add(offset).ok_or(TaskError::InvalidDeadline)?),
+                None => None,
+            },
+        };
+
+        Ok(NewTask {
+            task_id,
+            title: self.render_title(title_param),
+            deliverables: overrides.deliverables.unwrap_or_else(|| self.deliverables.clone()),
+            milestones: overrides.milestones.unwrap_or_else(|| self.milestones.clone()),
+            target_budget,
+            recipient,
+            deadline,
+            max_revisions: overrides.max_revisions.unwrap_or(self.max_revisions),
+            review_window_seconds: overrides
+                .review_window_seconds
+                .unwrap_or(self.review_window_seconds),
+            reviewers: overrides.reviewers.unwrap_or_else(|| self.reviewers.clone()),
+            approval_model: overrides.approval_model.unwrap_or_else(|| self.approval_model.clone()),
+            veto_threshold_bps: overrides.veto_threshold_bps.unwrap_or(self.veto_threshold_bps),
+            kind: TaskKind::Standard,
+        })
+    }
+}
+
+/// Instruction arguments for create_template (template_id is passed separately for PDA seeds)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateTemplateParams {
+    pub title_pattern: String,
+    pub deliverables: String,
+    pub default_deadline_offset: Option<i64>,
+    pub max_revisions: u8,
+    pub review_window_seconds: Option<i64>,
+    pub reviewers: Vec<Pubkey>,
+    pub approval_model: ApprovalModel,
+    pub veto_threshold_bps: u16,
+    pub milestones: Vec<Milestone>,
+}
+
+impl CreateTemplateParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            TaskTemplate::validate_title_pattern(&self.title_pattern),
+            TaskError::InvalidTitlePattern
+        );
+        require!(Task::validate_deliverables(&self.deliverables), TaskError::InvalidDeliverables);
+        require!(
+            !matches!(self.default_deadline_offset, Some(offset) if !(1..=MAX_DEADLINE_OFFSET_SECONDS).contains(&offset)),
+            TaskError::InvalidDeadlineOffset
+        );
+        require!(Task::validate_milestones(&self.milestones), TaskError::InvalidMilestones);
+        require!(self.max_revisions <= MAX_REVISIONS, TaskError::InvalidMaxRevisions);
+        require!(
+            (MIN_REVIEW_WINDOW_SECONDS..=MAX_REVIEW_WINDOW_SECONDS).contains(&self.review_window_seconds()),
+            TaskError::InvalidReviewWindow
+        );
+        require!(Task::validate_reviewers(&self.reviewers), TaskError::InvalidReviewers);
+        require!(
+            Task::validate_approval_model(&self.approval_model, &self.reviewers, self.veto_threshold_bps),
+            TaskError::InvalidApprovalModel
+        );
+
+        Ok(())
+    }
+
+    /// Review window stored on the template, falling back to the global default
+    pub fn review_window_seconds(&self) -> i64 {
+        self.review_window_seconds.unwrap_or(DEFAULT_REVIEW_WINDOW_SECONDS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template() -> TaskTemplate {
+        TaskTemplate {
+            template_id: "translate".to_string(),
+            creator: Pubkey::default(),
+            title_pattern: "Translate docs into {}".to_string(),
+            deliverables: "Reviewed translation of /docs".to_string(),
+            default_deadline_offset: Some(1_000),
+            approval_model: ApprovalModel::DonorVote,
+            veto_threshold_bps: 0,
+            max_revisions: 2,
+            review_window_seconds: 3 * 24 * 60 * 60,
+            reviewers: vec![],
+            milestones: vec![],
+            tasks_created: 0,
+            created_at: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_instantiate_with_defaults() {
+        let task = template().instantiate("t-es".to_string(), "Spanish", 500, None, TemplateOverrides::default(), 100).unwrap();
+
+        assert_eq!(task.title, "Translate docs into Spanish");
+        assert_eq!(task.deliverables, "Reviewed translation of /docs");
+        assert_eq!(task.deadline, Some(1_100));
+        assert_eq!(task.max_revisions, 2);
+        assert_eq!(task.review_window_seconds, 3 * 24 * 60 * 60);
+        assert!(task.validate().is_ok());
+    }
+
+    #[test]
+    fn test_instantiate_with_overrides() {
+        let overrides = TemplateOverrides {
+            deliverables: Some("Translation of /docs/api only".to_string()),
+            deadline: Some(42),
+            max_revisions: Some(0),
+            review_window_seconds: Some(MAX_REVIEW_WINDOW_SECONDS),
+            ..TemplateOverrides::default()
+        };
+        let task = template().instantiate("t-fr".to_string(), "French", 500, None, overrides, 100).unwrap();
+
+        assert_eq!(task.title, "Translate docs into French");
+        assert_eq!(task.deliverables, "Translation of /docs/api only");
+        assert_eq!(task.deadline, Some(42));
+        assert_eq!(task.max_revisions, 0);
+        assert_eq!(task.review_window_seconds, MAX_REVIEW_WINDOW_SECONDS);
+    }
+
+    #[test]
+    fn test_rendered_title_is_validated() {
+        let long_param = "x".repeat(MAX_TASK_TITLE_LEN);
+        let task = template().instantiate("t-long".to_string(), &long_param, 500, None, TemplateOverrides::default(), 0).unwrap();
+        assert!(task.validate().is_err());
+    }
+
+    #[test]
+    fn test_deadline_offset_overflow_fails() {
+        let mut template = template();
+        template.default_deadline_offset = Some(i64::MAX);
+        assert!(template.instantiate("t-x".to_string(), "X", 500, None, TemplateOverrides::default(), 1).is_err());
+
+        // An explicit deadline never touches the offset
+        let overrides = TemplateOverrides { deadline: Some(42), ..TemplateOverrides::default() };
+        assert!(template.instantiate("t-y".to_string(), "Y", 500, None, overrides, 1).is_ok());
+    }
+
+    #[test]
+    fn test_template_params_bound_deadline_offset() {
+        let mut params = CreateTemplateParams {
+            title_pattern: "Translate docs into {}".to_string(),
+            deliverables: "Reviewed translation of /docs".to_string(),
+            default_deadline_offset: None,
+            max_revisions: 2,
+            review_window_seconds: None,
+            reviewers: vec![],
+            approval_model: ApprovalModel::DonorVote,
+            veto_threshold_bps: 0,
+            milestones: vec![],
+        };
+        assert!(params.validate().is_ok());
+        assert_eq!(params.review_window_seconds(), DEFAULT_REVIEW_WINDOW_SECONDS);
+
+        params.review_window_seconds = Some(MIN_REVIEW_WINDOW_SECONDS - 1);
+        assert!(params.validate().is_err());
+        params.review_window_seconds = None;
+
+        params.default_deadline_offset = Some(MAX_DEADLINE_OFFSET_SECONDS);
+        assert!(params.validate().is_ok());
+
+        for offset in [0, -1, MAX_DEADLINE_OFFSET_SECONDS + 1, i64::MAX] {
+            params.default_deadline_offset = Some(offset);
+            assert!(params.validate().is_err());
+        }
+    }
+}